@@ -1,26 +1,222 @@
-use std::{mem, sync::{Arc, Mutex}};
+use std::{mem, sync::{Arc, Mutex}, thread};
 
-use crate::{context::{with_current_context, GLSharedState, GlContext, GLOBAL_STATE}, types::GlBool};
+use crate::{context::{set_kori_error, take_kori_error, with_current_context, ContextEntry, CurrentContext, GLSharedState, GlContext, CURRENT_CONTEXT, GLOBAL_STATE}, enums::{InternalFormat, KoriAttrib, KoriError, KORI_BACK_BUFFER, KORI_COLORSPACE_LINEAR, KORI_COLORSPACE_SRGB, KORI_COLOR_COMPONENT_TYPE_FIXED, KORI_COLOR_COMPONENT_TYPE_FLOAT, KORI_SINGLE_BUFFER}, types::{GlBool, SurfaceConfig}};
 
 
-#[unsafe(no_mangle)]
-pub extern "C" fn glKCreateContext(width: usize, height: usize, share_with: usize, double_buffered: GlBool) -> usize {
-    let context = GlContext::init(width, height, double_buffered);
+fn create_context(width: usize, height: usize, share_with: usize, config: SurfaceConfig) -> usize {
     let mut global_state = GLOBAL_STATE.lock().unwrap();
+    // Textures, shaders, programs, renderbuffers and buffers of `share_with` become visible to the new context.
+    let shared = if share_with != 0 {
+        match global_state.contexts.get(&share_with).filter(|entry| !entry.pending_destroy) {
+            Some(share_with_context) => share_with_context.shared.clone(),
+            None => {
+                set_kori_error(KoriError::BadContext);
+                return 0;
+            }
+        }
+    } else {
+        Arc::new(Mutex::new(GLSharedState::init()))
+    };
+    let context = GlContext::init(width, height, config, shared.clone());
     let context_id = global_state.next_context_id;
-    global_state.contexts.insert(context_id, context);
+    global_state.contexts.insert(context_id, ContextEntry { context: Arc::new(Mutex::new(context)), shared, bound_thread: None, pending_destroy: false });
     global_state.next_context_id += 1;
-    if share_with != 0 {
-        if let Some(share_with_context) = global_state.contexts.get(&share_with) {
-            with_current_context(|context| context.shared = Some(share_with_context.shared.clone().unwrap_or_else(|| Arc::new(Mutex::new(GLSharedState::init())))));
+    context_id
+}
+
+// Creates a context whose default framebuffer has an RGBA32F color buffer and a 32 bit float depth buffer.
+#[unsafe(no_mangle)]
+pub extern "C" fn glKCreateContext(width: usize, height: usize, share_with: usize, double_buffered: GlBool) -> usize {
+    let config = SurfaceConfig {
+        color_format: InternalFormat::Rgba32F,
+        depth_format: Some(InternalFormat::DepthComponent32F),
+        stencil_bits: 0,
+        srgb: false,
+        samples: 0,
+        double_buffered: double_buffered != 0,
+    };
+    create_context(width, height, share_with, config)
+}
+
+// Builds the default framebuffer format from a KoriAttrib::None terminated list of key/value pairs.
+// Unspecified attributes default to a double buffered, linear RGBA8 surface without depth, stencil or multisampling.
+fn parse_attrib_list(attrib_list: *const i32) -> Result<SurfaceConfig, KoriError> {
+    let (mut red, mut green, mut blue, mut alpha) = (8, 8, 8, 8);
+    let (mut depth, mut stencil, mut samples) = (0, 0, 0);
+    let mut component_type = KORI_COLOR_COMPONENT_TYPE_FIXED;
+    let mut srgb = false;
+    let mut double_buffered = true;
+    if !attrib_list.is_null() {
+        let mut i = 0;
+        loop {
+            let key = unsafe { *attrib_list.add(i) };
+            let Some(key) = KoriAttrib::from_u32(key as u32) else {
+                return Err(KoriError::BadAttribute);
+            };
+            if key == KoriAttrib::None {
+                break;
+            }
+            let value = unsafe { *attrib_list.add(i + 1) };
+            match key {
+                KoriAttrib::RenderBuffer => match value {
+                    KORI_BACK_BUFFER => double_buffered = true,
+                    KORI_SINGLE_BUFFER => double_buffered = false,
+                    _ => return Err(KoriError::BadAttribute),
+                },
+                KoriAttrib::ColorSpace => match value {
+                    KORI_COLORSPACE_SRGB => srgb = true,
+                    KORI_COLORSPACE_LINEAR => srgb = false,
+                    _ => return Err(KoriError::BadAttribute),
+                },
+                KoriAttrib::ColorComponentType => match value {
+                    KORI_COLOR_COMPONENT_TYPE_FIXED | KORI_COLOR_COMPONENT_TYPE_FLOAT => component_type = value,
+                    _ => return Err(KoriError::BadAttribute),
+                },
+                _ if value < 0 => return Err(KoriError::BadAttribute),
+                KoriAttrib::RedSize => red = value,
+                KoriAttrib::GreenSize => green = value,
+                KoriAttrib::BlueSize => blue = value,
+                KoriAttrib::AlphaSize => alpha = value,
+                KoriAttrib::DepthSize => depth = value,
+                KoriAttrib::StencilSize => stencil = value,
+                KoriAttrib::Samples => samples = value,
+                KoriAttrib::None => unreachable!(),
+            }
+            i += 2;
+        }
+    }
+    let color_format = match (component_type, red, green, blue, alpha) {
+        (KORI_COLOR_COMPONENT_TYPE_FIXED, 8, 8, 8, 8) => InternalFormat::Rgba8,
+        (KORI_COLOR_COMPONENT_TYPE_FIXED, 8, 8, 8, 0) => InternalFormat::Rgb8,
+        (KORI_COLOR_COMPONENT_TYPE_FIXED, 10, 10, 10, 2) => InternalFormat::Rgb10A2,
+        (KORI_COLOR_COMPONENT_TYPE_FIXED, 5, 6, 5, 0) => InternalFormat::Rgb565,
+        (KORI_COLOR_COMPONENT_TYPE_FLOAT, 16, 16, 16, 16) => InternalFormat::Rgba16F,
+        (KORI_COLOR_COMPONENT_TYPE_FLOAT, 32, 32, 32, 32) => InternalFormat::Rgba32F,
+        _ => return Err(KoriError::BadMatch),
+    };
+    let depth_format = match depth {
+        0 => None,
+        16 => Some(InternalFormat::DepthComponent16),
+        24 => Some(InternalFormat::DepthComponent24),
+        32 => Some(InternalFormat::DepthComponent32F),
+        _ => return Err(KoriError::BadMatch),
+    };
+    if !matches!(stencil, 0 | 8) || !matches!(samples, 0 | 2 | 4 | 8 | 16) {
+        return Err(KoriError::BadMatch);
+    }
+    // sRGB encoding is only defined for 8 bit fixed point formats
+    if srgb && !matches!(color_format, InternalFormat::Rgba8 | InternalFormat::Rgb8) {
+        return Err(KoriError::BadMatch);
+    }
+    Ok(SurfaceConfig {
+        color_format,
+        depth_format,
+        stencil_bits: stencil as u32,
+        srgb,
+        samples: samples as u32,
+        double_buffered,
+    })
+}
+
+// Creates a context with the default framebuffer format described by `attrib_list`, see `parse_attrib_list`.
+// A null list selects the defaults.
+#[unsafe(no_mangle)]
+pub extern "C" fn glKCreateContextAttribs(width: usize, height: usize, share_with: usize, attrib_list: *const i32) -> usize {
+    match parse_attrib_list(attrib_list) {
+        Ok(config) => create_context(width, height, share_with, config),
+        Err(error) => {
+            set_kori_error(error);
+            0
         }
     }
-    context_id
+}
+
+// Makes `context_id` current on the calling thread, releasing the previously current context.
+// Passing 0 releases the current context without binding a new one.
+// Returns 0 and leaves the current binding untouched on failure.
+#[unsafe(no_mangle)]
+pub extern "C" fn glKMakeCurrent(context_id: usize) -> GlBool {
+    let current_id = CURRENT_CONTEXT.with_borrow(|current| current.as_ref().map(|current| current.id));
+    if current_id == Some(context_id) {
+        return 1;
+    }
+    let binding = if context_id == 0 {
+        None
+    } else {
+        let mut global_state = GLOBAL_STATE.lock().unwrap();
+        let Some(entry) = global_state.contexts.get_mut(&context_id).filter(|entry| !entry.pending_destroy) else {
+            set_kori_error(KoriError::BadContext);
+            return 0;
+        };
+        if entry.bound_thread.is_some() {
+            // Current on another thread
+            set_kori_error(KoriError::BadAccess);
+            return 0;
+        }
+        let thread = thread::current().id();
+        entry.bound_thread = Some(thread);
+        Some(CurrentContext { id: context_id, context: entry.context.clone(), thread })
+    };
+    // The previous binding is dropped outside of the global lock, as releasing it locks the global state itself.
+    let previous = CURRENT_CONTEXT.replace(binding);
+    drop(previous);
+    1
+}
+
+// Destroys `context_id`. Its framebuffers are released and its reference to the shared state is dropped.
+// A context that is current on any thread stays usable there and is only destroyed once it is released, like in EGL.
+#[unsafe(no_mangle)]
+pub extern "C" fn glKDestroyContext(context_id: usize) -> GlBool {
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let Some(entry) = global_state.contexts.get_mut(&context_id).filter(|entry| !entry.pending_destroy) else {
+        set_kori_error(KoriError::BadContext);
+        return 0;
+    };
+    if entry.bound_thread.is_some() {
+        entry.pending_destroy = true;
+        return 1;
+    }
+    let entry = global_state.contexts.remove(&context_id);
+    drop(global_state);
+    // Not current anywhere, so this is the last reference and the whole context is freed here.
+    drop(entry);
+    1
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glKGetCurrentContext() -> usize {
+    CURRENT_CONTEXT.with_borrow(|current| current.as_ref().map_or(0, |current| current.id))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glKGetError() -> u32 {
+    take_kori_error() as u32
+}
+
+// Resizes every buffer of the current context's default framebuffer.
+// With `preserve_contents` the overlapping region keeps its contents, otherwise all buffers are zeroed.
+#[unsafe(no_mangle)]
+pub extern "C" fn glKResizeSurface(width: usize, height: usize, preserve_contents: GlBool) -> GlBool {
+    if width == 0 || height == 0 {
+        set_kori_error(KoriError::BadParameter);
+        return 0;
+    }
+    with_current_context(|context| {
+        context.default_framebuffer.resize(width, height, preserve_contents != 0);
+        1
+    })
+    .unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn glKSwapBuffers() {
     with_current_context(|context| {
-        mem::swap(&mut context.default_framebuffer.color_buffer_back, &mut context.default_framebuffer.color_buffer_front);
+        let framebuffer = &mut context.default_framebuffer;
+        // A multisampled back buffer is resolved into the front buffer and keeps its contents
+        if framebuffer.color_buffer_back.samples > 0 {
+            framebuffer.color_buffer_back.resolve_into(&mut framebuffer.color_buffer_front);
+        } else {
+            mem::swap(&mut framebuffer.color_buffer_back, &mut framebuffer.color_buffer_front);
+        }
     });
-}
\ No newline at end of file
+}
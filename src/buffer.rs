@@ -0,0 +1,483 @@
+use std::{
+    ops::Range,
+    ptr, slice,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    context::{with_current_context, GlContext},
+    enums::{
+        BufferAccess, BufferParameter, BufferTarget, BufferUsage, ErrorCode, GL_BUFFER_MAP_POINTER,
+        GL_CLIENT_STORAGE_BIT, GL_DYNAMIC_STORAGE_BIT, GL_MAP_COHERENT_BIT, GL_MAP_FLUSH_EXPLICIT_BIT,
+        GL_MAP_INVALIDATE_BUFFER_BIT, GL_MAP_INVALIDATE_RANGE_BIT, GL_MAP_PERSISTENT_BIT, GL_MAP_READ_BIT,
+        GL_MAP_UNSYNCHRONIZED_BIT, GL_MAP_WRITE_BIT,
+    },
+    states::IndexedBufferBinding,
+    types::{Buffer, BufferMapping, GlBitfield, GlBool, GlIntptr, GlSizei, GlSizeiptr},
+};
+
+// Buffer bound to `target` in the current context, if any
+pub(crate) fn bound_buffer_object(context: &GlContext, target: BufferTarget) -> Option<Arc<Mutex<Buffer>>> {
+    let buffer = context.buffer_binding(target);
+    context.shared.lock().unwrap().buffers.objects.get(&buffer).cloned()
+}
+
+// Buffer bound to `target`, recording the matching error when there is none
+fn bound_buffer(context: &mut GlContext, target: u32) -> Option<Arc<Mutex<Buffer>>> {
+    let Some(target) = BufferTarget::from_u32(target) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    };
+    let buffer = bound_buffer_object(context, target);
+    if buffer.is_none() {
+        context.set_error(ErrorCode::InvalidOperation);
+    }
+    buffer
+}
+
+// Bytes `offset..offset + size` of a buffer holding `len` bytes, recording GL_INVALID_VALUE when they don't fit
+fn buffer_range(context: &mut GlContext, len: usize, offset: GlIntptr, size: GlSizeiptr) -> Option<Range<usize>> {
+    if offset < 0 || size < 0 || offset as usize + size as usize > len {
+        context.set_error(ErrorCode::InvalidValue);
+        return None;
+    }
+    Some(offset as usize..offset as usize + size as usize)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGenBuffers(n: GlSizei, buffers: *mut u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || buffers.is_null() {
+            return;
+        }
+        let buffer_ids = unsafe { slice::from_raw_parts_mut(buffers, n as usize) };
+        let mut shared = context.shared.lock().unwrap();
+        for id in buffer_ids.iter_mut() {
+            *id = shared.buffers.insert(Arc::new(Mutex::new(Buffer::new())));
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteBuffers(n: GlSizei, buffers: *const u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || buffers.is_null() {
+            return;
+        }
+        let buffer_ids = unsafe { slice::from_raw_parts(buffers, n as usize) };
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        for &key in buffer_ids {
+            if shared.buffers.objects.remove(&key).is_none() {
+                continue;
+            }
+            // Like textures, only the bindings of the current context and its bound vertex array revert to zero
+            context.buffer_bindings.retain(|_, bound| *bound != key);
+            context.indexed_buffer_bindings.retain(|_, binding| binding.buffer != key);
+            let vertex_array = context.vertex_array();
+            if vertex_array.element_array_buffer == key {
+                vertex_array.element_array_buffer = 0;
+            }
+            for binding in vertex_array.bindings.iter_mut().filter(|binding| binding.buffer == key) {
+                binding.buffer = 0;
+            }
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsBuffer(buffer: u32) -> GlBool {
+    with_current_context(|context| {
+        let shared = context.shared.lock().unwrap();
+        shared
+            .buffers
+            .objects
+            .get(&buffer)
+            .is_some_and(|buffer| buffer.lock().unwrap().created) as GlBool
+    })
+    .unwrap_or(0)
+}
+
+// Marks `buffer` as created, recording GL_INVALID_OPERATION for names that didn't come from glGenBuffers
+pub(crate) fn create_buffer(context: &mut GlContext, buffer: u32) -> bool {
+    let shared_state = context.shared.clone();
+    let shared = shared_state.lock().unwrap();
+    let Some(buffer) = shared.buffers.objects.get(&buffer) else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return false;
+    };
+    buffer.lock().unwrap().created = true;
+    true
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindBuffer(target: u32, buffer: u32) {
+    with_current_context(|context| {
+        let Some(target) = BufferTarget::from_u32(target) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        if buffer == 0 || create_buffer(context, buffer) {
+            context.bind_buffer(target, buffer);
+        }
+    });
+}
+
+// Binds `buffer` to the indexed binding point `index` of `target` and to the generic binding of `target`.
+// A `range` of None binds the whole buffer.
+fn bind_buffer_range(context: &mut GlContext, target: u32, index: u32, buffer: u32, range: Option<(GlIntptr, GlSizeiptr)>) {
+    let Some(target) = BufferTarget::from_u32(target).filter(|target| target.binding_points().is_some()) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    if target.binding_points().is_some_and(|count| index >= count) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    if let Some((offset, size)) = range
+        && buffer != 0
+    {
+        if offset < 0 || size <= 0 || !(offset as usize).is_multiple_of(target.offset_alignment()) {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        // Transform feedback writes whole words
+        if target == BufferTarget::TransformFeedback && !(size as usize).is_multiple_of(4) {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+    }
+    if buffer == 0 {
+        context.bind_buffer(target, 0);
+        context.indexed_buffer_bindings.remove(&(target, index));
+        return;
+    }
+    if !create_buffer(context, buffer) {
+        return;
+    }
+    let (offset, size) = match range {
+        Some((offset, size)) => (offset as usize, Some(size as usize)),
+        None => (0, None),
+    };
+    context.bind_buffer(target, buffer);
+    context.indexed_buffer_bindings.insert((target, index), IndexedBufferBinding { buffer, offset, size });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindBufferBase(target: u32, index: u32, buffer: u32) {
+    with_current_context(|context| bind_buffer_range(context, target, index, buffer, None));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindBufferRange(target: u32, index: u32, buffer: u32, offset: GlIntptr, size: GlSizeiptr) {
+    with_current_context(|context| bind_buffer_range(context, target, index, buffer, Some((offset, size))));
+}
+
+// `size` bytes copied from `data`, or zeroed when `data` is null. Records GL_OUT_OF_MEMORY and returns None when
+// they can't be allocated.
+fn initial_data(context: &mut GlContext, size: GlSizeiptr, data: *const u8) -> Option<Vec<u8>> {
+    let mut initial = Vec::new();
+    if initial.try_reserve_exact(size as usize).is_err() {
+        context.set_error(ErrorCode::OutOfMemory);
+        return None;
+    }
+    if data.is_null() {
+        initial.resize(size as usize, 0u8);
+    } else {
+        initial.extend_from_slice(unsafe { slice::from_raw_parts(data, size as usize) });
+    }
+    Some(initial)
+}
+
+// Replaces the storage of the bound buffer, unmapping it first
+#[unsafe(no_mangle)]
+pub extern "C" fn glBufferData(target: u32, size: GlSizeiptr, data: *const u8, usage: u32) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        if size < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let Some(usage) = BufferUsage::from_u32(usage) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let mut buffer = buffer.lock().unwrap();
+        if buffer.immutable {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        let Some(initial) = initial_data(context, size, data) else {
+            return;
+        };
+        buffer.mapping = None;
+        buffer.data = initial;
+        buffer.usage = usage;
+        // Mutable storage can be mapped for reading and writing and updated, but never persistently
+        buffer.storage_flags = GL_MAP_READ_BIT | GL_MAP_WRITE_BIT | GL_DYNAMIC_STORAGE_BIT;
+    });
+}
+
+// Allocates immutable storage for the bound buffer. `flags` limit how it can be mapped and updated later.
+#[unsafe(no_mangle)]
+pub extern "C" fn glBufferStorage(target: u32, size: GlSizeiptr, data: *const u8, flags: GlBitfield) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        let known_bits = GL_MAP_READ_BIT
+            | GL_MAP_WRITE_BIT
+            | GL_MAP_PERSISTENT_BIT
+            | GL_MAP_COHERENT_BIT
+            | GL_DYNAMIC_STORAGE_BIT
+            | GL_CLIENT_STORAGE_BIT;
+        let invalid_flags = flags & !known_bits != 0
+            || flags & GL_MAP_PERSISTENT_BIT != 0 && flags & (GL_MAP_READ_BIT | GL_MAP_WRITE_BIT) == 0
+            || flags & GL_MAP_COHERENT_BIT != 0 && flags & GL_MAP_PERSISTENT_BIT == 0;
+        if size <= 0 || invalid_flags {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let mut buffer = buffer.lock().unwrap();
+        if buffer.immutable {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        let Some(initial) = initial_data(context, size, data) else {
+            return;
+        };
+        buffer.mapping = None;
+        buffer.data = initial;
+        buffer.immutable = true;
+        buffer.storage_flags = flags;
+        // Usage hints don't apply to immutable storage
+        buffer.usage = BufferUsage::DynamicDraw;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBufferSubData(target: u32, offset: GlIntptr, size: GlSizeiptr, data: *const u8) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        let mut buffer = buffer.lock().unwrap();
+        let Some(range) = buffer_range(context, buffer.data.len(), offset, size) else {
+            return;
+        };
+        // Immutable storage can only be updated when created with GL_DYNAMIC_STORAGE_BIT
+        if buffer.is_mapped_exclusively() || buffer.immutable && buffer.storage_flags & GL_DYNAMIC_STORAGE_BIT == 0 {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        if data.is_null() || range.is_empty() {
+            return;
+        }
+        buffer.data[range].copy_from_slice(unsafe { slice::from_raw_parts(data, size as usize) });
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBufferSubData(target: u32, offset: GlIntptr, size: GlSizeiptr, data: *mut u8) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        let buffer = buffer.lock().unwrap();
+        let Some(range) = buffer_range(context, buffer.data.len(), offset, size) else {
+            return;
+        };
+        if buffer.is_mapped_exclusively() {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        if data.is_null() || range.is_empty() {
+            return;
+        }
+        unsafe { slice::from_raw_parts_mut(data, size as usize) }.copy_from_slice(&buffer.data[range]);
+    });
+}
+
+// Maps `length` bytes of the bound buffer starting at `offset`. The returned pointer points straight into the
+// storage of the buffer, so every mapping is coherent and flushing and invalidating have nothing to do.
+fn map_buffer_range(context: &mut GlContext, target: u32, offset: GlIntptr, length: GlSizeiptr, access: GlBitfield) -> *mut u8 {
+    let Some(buffer) = bound_buffer(context, target) else {
+        return ptr::null_mut();
+    };
+    let mut buffer = buffer.lock().unwrap();
+    let known_bits = GL_MAP_READ_BIT
+        | GL_MAP_WRITE_BIT
+        | GL_MAP_INVALIDATE_RANGE_BIT
+        | GL_MAP_INVALIDATE_BUFFER_BIT
+        | GL_MAP_FLUSH_EXPLICIT_BIT
+        | GL_MAP_UNSYNCHRONIZED_BIT
+        | GL_MAP_PERSISTENT_BIT
+        | GL_MAP_COHERENT_BIT;
+    if length <= 0 || access & !known_bits != 0 {
+        context.set_error(ErrorCode::InvalidValue);
+        return ptr::null_mut();
+    }
+    let Some(range) = buffer_range(context, buffer.data.len(), offset, length) else {
+        return ptr::null_mut();
+    };
+    let reads = access & GL_MAP_READ_BIT != 0;
+    let writes = access & GL_MAP_WRITE_BIT != 0;
+    // Only the bits the storage was created with can be used, invalidating and skipping synchronization only
+    // make sense for writes
+    let storage_bits = GL_MAP_READ_BIT | GL_MAP_WRITE_BIT | GL_MAP_PERSISTENT_BIT | GL_MAP_COHERENT_BIT;
+    let write_bits = GL_MAP_INVALIDATE_RANGE_BIT | GL_MAP_INVALIDATE_BUFFER_BIT | GL_MAP_UNSYNCHRONIZED_BIT;
+    if buffer.mapping.is_some()
+        || !reads && !writes
+        || reads && access & write_bits != 0
+        || !writes && access & GL_MAP_FLUSH_EXPLICIT_BIT != 0
+        || access & storage_bits & !buffer.storage_flags != 0
+    {
+        context.set_error(ErrorCode::InvalidOperation);
+        return ptr::null_mut();
+    }
+    buffer.mapping = Some(BufferMapping { offset: range.start, length: range.len(), access });
+    buffer.data[range].as_mut_ptr()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glMapBufferRange(target: u32, offset: GlIntptr, length: GlSizeiptr, access: GlBitfield) -> *mut u8 {
+    with_current_context(|context| map_buffer_range(context, target, offset, length, access)).unwrap_or(ptr::null_mut())
+}
+
+// Maps the whole bound buffer
+#[unsafe(no_mangle)]
+pub extern "C" fn glMapBuffer(target: u32, access: u32) -> *mut u8 {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return ptr::null_mut();
+        };
+        let Some(access) = BufferAccess::from_u32(access) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return ptr::null_mut();
+        };
+        let access = match access {
+            BufferAccess::ReadOnly => GL_MAP_READ_BIT,
+            BufferAccess::WriteOnly => GL_MAP_WRITE_BIT,
+            BufferAccess::ReadWrite => GL_MAP_READ_BIT | GL_MAP_WRITE_BIT,
+        };
+        let size = buffer.lock().unwrap().data.len();
+        map_buffer_range(context, target, 0, size as GlSizeiptr, access)
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+// Only validates the range, writes through the mapping are visible right away
+#[unsafe(no_mangle)]
+pub extern "C" fn glFlushMappedBufferRange(target: u32, offset: GlIntptr, length: GlSizeiptr) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        let buffer = buffer.lock().unwrap();
+        let Some(mapping) = buffer.mapping.filter(|mapping| mapping.access & GL_MAP_FLUSH_EXPLICIT_BIT != 0) else {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        };
+        // Relative to the start of the mapping
+        buffer_range(context, mapping.length, offset, length);
+    });
+}
+
+// Returns GL_FALSE only for buffers that weren't mapped. The contents of a buffer can't get lost while mapped.
+#[unsafe(no_mangle)]
+pub extern "C" fn glUnmapBuffer(target: u32) -> GlBool {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return 0;
+        };
+        if buffer.lock().unwrap().mapping.take().is_none() {
+            context.set_error(ErrorCode::InvalidOperation);
+            return 0;
+        }
+        1
+    })
+    .unwrap_or(0)
+}
+
+fn get_buffer_parameter(target: u32, pname: u32) -> Option<i64> {
+    with_current_context(|context| {
+        let buffer = bound_buffer(context, target)?;
+        let Some(pname) = BufferParameter::from_u32(pname) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return None;
+        };
+        let buffer = buffer.lock().unwrap();
+        let mapping = buffer.mapping;
+        let value = match pname {
+            BufferParameter::ImmutableStorage => buffer.immutable as i64,
+            BufferParameter::StorageFlags => buffer.storage_flags as i64,
+            BufferParameter::Size => buffer.data.len() as i64,
+            BufferParameter::Usage => buffer.usage as i64,
+            BufferParameter::Access => {
+                let access = mapping.map_or(GL_MAP_READ_BIT | GL_MAP_WRITE_BIT, |mapping| mapping.access);
+                let access = match (access & GL_MAP_READ_BIT != 0, access & GL_MAP_WRITE_BIT != 0) {
+                    (true, false) => BufferAccess::ReadOnly,
+                    (false, true) => BufferAccess::WriteOnly,
+                    _ => BufferAccess::ReadWrite,
+                };
+                access as i64
+            }
+            BufferParameter::Mapped => mapping.is_some() as i64,
+            BufferParameter::AccessFlags => mapping.map_or(0, |mapping| mapping.access as i64),
+            BufferParameter::MapLength => mapping.map_or(0, |mapping| mapping.length as i64),
+            BufferParameter::MapOffset => mapping.map_or(0, |mapping| mapping.offset as i64),
+        };
+        Some(value)
+    })
+    .flatten()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBufferParameteriv(target: u32, pname: u32, params: *mut i32) {
+    if let Some(value) = get_buffer_parameter(target, pname)
+        && !params.is_null()
+    {
+        unsafe { *params = value.clamp(i32::MIN as i64, i32::MAX as i64) as i32 };
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBufferParameteri64v(target: u32, pname: u32, params: *mut i64) {
+    if let Some(value) = get_buffer_parameter(target, pname)
+        && !params.is_null()
+    {
+        unsafe { *params = value };
+    }
+}
+
+// Pointer returned by the mapping of the bound buffer, null when it isn't mapped
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBufferPointerv(target: u32, pname: u32, params: *mut *mut u8) {
+    with_current_context(|context| {
+        let Some(buffer) = bound_buffer(context, target) else {
+            return;
+        };
+        if pname != GL_BUFFER_MAP_POINTER {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+        let mut buffer = buffer.lock().unwrap();
+        let pointer = match buffer.mapping {
+            Some(mapping) => buffer.data[mapping.offset..].as_mut_ptr(),
+            None => ptr::null_mut(),
+        };
+        if !params.is_null() {
+            unsafe { *params = pointer };
+        }
+    });
+}
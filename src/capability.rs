@@ -0,0 +1,86 @@
+use crate::{context::with_current_context, enums::{Capability, ErrorCode}, types::GlBool};
+
+fn set_capability(cap: u32, enabled: bool) {
+    with_current_context(|context| {
+        let Some(capability) = Capability::from_u32(cap) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        // Non indexed enables set every index at once
+        for index in 0..capability.indices().max(1) {
+            let state = context.capability_state(capability, index);
+            if enabled {
+                state.enable();
+            } else {
+                state.disable();
+            }
+        }
+    });
+}
+
+fn set_capability_indexed(target: u32, index: u32, enabled: bool) {
+    with_current_context(|context| {
+        let Some(capability) = Capability::from_u32(target).filter(|capability| capability.indices() != 0) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        if index as usize >= capability.indices() {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let state = context.capability_state(capability, index as usize);
+        if enabled {
+            state.enable();
+        } else {
+            state.disable();
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glEnable(cap: u32) {
+    set_capability(cap, true);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDisable(cap: u32) {
+    set_capability(cap, false);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsEnabled(cap: u32) -> GlBool {
+    with_current_context(|context| {
+        let Some(capability) = Capability::from_u32(cap) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return 0;
+        };
+        context.capability_state(capability, 0).get_state() as GlBool
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glEnablei(target: u32, index: u32) {
+    set_capability_indexed(target, index, true);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDisablei(target: u32, index: u32) {
+    set_capability_indexed(target, index, false);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsEnabledi(target: u32, index: u32) -> GlBool {
+    with_current_context(|context| {
+        let Some(capability) = Capability::from_u32(target).filter(|capability| capability.indices() != 0) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return 0;
+        };
+        if index as usize >= capability.indices() {
+            context.set_error(ErrorCode::InvalidValue);
+            return 0;
+        }
+        context.capability_state(capability, index as usize).get_state() as GlBool
+    })
+    .unwrap_or(0)
+}
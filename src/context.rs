@@ -1,12 +1,21 @@
-use std::{collections::HashMap, sync::{Arc, LazyLock, Mutex}};
+use std::{cell::{Cell, RefCell}, collections::HashMap, sync::{Arc, LazyLock, Mutex}, thread::ThreadId};
 
-use crate::{enums::Framebuffer, states::{ClearState, FramebufferState}, types::{DefaultFramebuffer, GlBool, Scissor, FBO}};
+use crate::{enums::{BufferTarget, Capability, ErrorCode, Framebuffer, KoriError, TextureTarget, GL_MAX_DRAW_BUFFERS}, states::{BlendState, ClearState, ColorMask, CullState, DepthState, EnableFlag, FramebufferState, IndexedBufferBinding, PixelStoreState, StencilState}, types::{Buffer, DefaultFramebuffer, VertexArray, Enabelable, GlSizei, Renderbuffer, Scissor, ShaderObject, SurfaceConfig, Texture, FBO}};
 
 
+pub(crate) struct ContextEntry {
+    pub context: Arc<Mutex<GlContext>>,
+    // Same as the context's `shared`, so contexts sharing with it don't need to lock the context itself
+    pub shared: Arc<Mutex<GLSharedState>>,
+    // Thread the context is current on. A context can only be current on one thread at a time.
+    pub bound_thread: Option<ThreadId>,
+    // Set when the context is destroyed while current. It is removed once released by its thread.
+    pub pending_destroy: bool,
+}
+
 pub(crate) struct GlobalState {
-    pub contexts: HashMap<usize, GlContext>,
+    pub contexts: HashMap<usize, ContextEntry>,
     pub next_context_id: usize,
-    pub current_context: usize,
 }
 
 impl GlobalState {
@@ -14,7 +23,6 @@ impl GlobalState {
         Self {
             contexts: HashMap::new(),
             next_context_id: 1,
-            current_context: 1,
         }
     }
 }
@@ -22,61 +30,232 @@ impl GlobalState {
 pub(crate) static GLOBAL_STATE: LazyLock<Mutex<GlobalState>> =
     LazyLock::new(|| Mutex::new(GlobalState::init()));
 
-pub fn with_current_context<F, R>(f: F) -> R
+// Binding of a context to the calling thread. Dropping it, either through glKMakeCurrent or on thread exit,
+// releases the context so it can be made current elsewhere.
+pub(crate) struct CurrentContext {
+    pub id: usize,
+    pub context: Arc<Mutex<GlContext>>,
+    // Thread the binding was made on. Kept here because thread::current() can't be relied on while thread locals
+    // are destroyed on thread exit.
+    pub thread: ThreadId,
+}
+
+impl Drop for CurrentContext {
+    fn drop(&mut self) {
+        let mut state = GLOBAL_STATE.lock().unwrap();
+        if let Some(entry) = state.contexts.get_mut(&self.id)
+            && entry.bound_thread == Some(self.thread)
+        {
+            entry.bound_thread = None;
+            if entry.pending_destroy {
+                state.contexts.remove(&self.id);
+            }
+        }
+    }
+}
+
+thread_local! {
+    pub(crate) static CURRENT_CONTEXT: RefCell<Option<CurrentContext>> = const { RefCell::new(None) };
+    static KORI_ERROR: Cell<KoriError> = const { Cell::new(KoriError::Success) };
+}
+
+pub(crate) fn set_kori_error(error: KoriError) {
+    KORI_ERROR.set(error);
+}
+
+pub(crate) fn take_kori_error() -> KoriError {
+    KORI_ERROR.replace(KoriError::Success)
+}
+
+// Runs `f` on the context current on the calling thread.
+// Without a current context nothing is run and KoriError::BadContext is recorded, as there is no GL error flag to set.
+pub fn with_current_context<F, R>(f: F) -> Option<R>
 where
     F: FnOnce(&mut GlContext) -> R,
 {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    let current = state.current_context;
-    let ctx = state.contexts.get_mut(&current).unwrap();
-    f(ctx)
+    CURRENT_CONTEXT.with_borrow(|current| match current {
+        Some(current) => Some(f(&mut current.context.lock().unwrap())),
+        None => {
+            set_kori_error(KoriError::BadContext);
+            None
+        }
+    })
+}
+
+// Names and objects of one object type. Names are never reused.
+pub(crate) struct ObjectNamespace<T> {
+    next_id: u32,
+    pub objects: HashMap<u32, T>,
+}
+
+impl<T> ObjectNamespace<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 1,
+            objects: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, object: T) -> u32 {
+        let id = self.next_id;
+        self.objects.insert(id, object);
+        self.next_id += 1;
+        id
+    }
 }
 
 // Shared state that can be shared between contexts.
+// Framebuffer objects are container objects and stay per context.
 pub(crate) struct GLSharedState {
-    //pub textures: RwLock<HashMap<>>
+    pub textures: ObjectNamespace<Arc<Mutex<Texture>>>,
+    pub shader_objects: ObjectNamespace<ShaderObject>,
+    // Framebuffer attachments hold their own reference, so deleting a name doesn't free attached storage
+    pub renderbuffers: ObjectNamespace<Arc<Mutex<Renderbuffer>>>,
+    pub buffers: ObjectNamespace<Arc<Mutex<Buffer>>>,
 }
 impl GLSharedState {
     pub(crate) fn init() -> Self {
-        Self {}
+        Self {
+            textures: ObjectNamespace::new(),
+            shader_objects: ObjectNamespace::new(),
+            renderbuffers: ObjectNamespace::new(),
+            buffers: ObjectNamespace::new(),
+        }
     }
 }
 
 pub struct GlContext {
-    pub shared: Option<Arc<Mutex<GLSharedState>>>,
+    pub shared: Arc<Mutex<GLSharedState>>,
     pub clear_state: ClearState,
     pub next_fb_id: u32,
     pub framebuffer_objects: HashMap<u32, FBO>,
+    pub next_vertex_array_id: u32,
+    // Vertex array object 0 always exists and is used while no other one is bound
+    pub vertex_arrays: HashMap<u32, VertexArray>,
+    pub vertex_array_binding: u32,
     pub default_framebuffer: DefaultFramebuffer,
     pub framebuffer_state: FramebufferState,
     pub viewport: Viewport,
     pub scissor: Scissor,
+    pub error: ErrorCode,
+    // Texture names bound to each target
+    pub texture_bindings: HashMap<TextureTarget, u32>,
+    pub renderbuffer_binding: u32,
+    // Buffer names bound to each target but GL_ELEMENT_ARRAY_BUFFER, and to each indexed binding point of the
+    // indexed targets
+    pub buffer_bindings: HashMap<BufferTarget, u32>,
+    pub indexed_buffer_bindings: HashMap<(BufferTarget, u32), IndexedBufferBinding>,
+    pub depth_state: DepthState,
+    pub stencil_state: StencilState,
+    // Indexed by draw buffer
+    pub blend_state: [BlendState; GL_MAX_DRAW_BUFFERS],
+    // Indexed by draw buffer
+    pub color_masks: [ColorMask; GL_MAX_DRAW_BUFFERS],
+    pub cull_state: CullState,
+    pub capability_flags: HashMap<Capability, EnableFlag>,
+    pub pack_state: PixelStoreState,
+    pub unpack_state: PixelStoreState,
 }
 
 impl GlContext {
-    pub fn init(width: usize, height: usize, double_buffered: GlBool) -> Self {
+    pub(crate) fn init(width: usize, height: usize, config: SurfaceConfig, shared: Arc<Mutex<GLSharedState>>) -> Self {
         let framebuffer_objects = HashMap::with_capacity(1);
-        let system_fb = DefaultFramebuffer::init(width, height, double_buffered);
+        let system_fb = DefaultFramebuffer::init(width, height, config);
         Self {
-            shared: None,
+            shared,
             clear_state: ClearState::default(),
             next_fb_id: 1,
             framebuffer_objects,
+            next_vertex_array_id: 1,
+            vertex_arrays: HashMap::from([(0, VertexArray::new())]),
+            vertex_array_binding: 0,
             default_framebuffer: system_fb,
             framebuffer_state: FramebufferState {
                 read_framebuffer: Framebuffer::Default,
                 write_framebuffer: Framebuffer::Default,
             },
-            viewport: Viewport::default(),
-            scissor: Scissor::default(),
+            // Both start out covering the whole default framebuffer
+            viewport: Viewport {
+                x: 0,
+                y: 0,
+                width: width as i32,
+                height: height as i32,
+            },
+            scissor: Scissor::new(0, 0, width as GlSizei, height as GlSizei),
+            error: ErrorCode::NoError,
+            texture_bindings: HashMap::new(),
+            renderbuffer_binding: 0,
+            buffer_bindings: HashMap::new(),
+            indexed_buffer_bindings: HashMap::new(),
+            depth_state: DepthState::default(),
+            stencil_state: StencilState::default(),
+            blend_state: [BlendState::default(); GL_MAX_DRAW_BUFFERS],
+            color_masks: [ColorMask::default(); GL_MAX_DRAW_BUFFERS],
+            cull_state: CullState::default(),
+            // Everything but dithering and multisampling starts out disabled
+            capability_flags: HashMap::from([
+                (Capability::Dither, EnableFlag { enabled: true }),
+                (Capability::Multisample, EnableFlag { enabled: true }),
+            ]),
+            pack_state: PixelStoreState::default(),
+            unpack_state: PixelStoreState::default(),
+        }
+    }
+
+    // State backing `capability` at `index`. Non indexed capabilities only have index 0.
+    pub(crate) fn capability_state(&mut self, capability: Capability, index: usize) -> &mut dyn Enabelable {
+        match capability {
+            Capability::ScissorTest => &mut self.scissor,
+            Capability::DepthTest => &mut self.depth_state,
+            Capability::StencilTest => &mut self.stencil_state,
+            Capability::Blend => &mut self.blend_state[index],
+            Capability::CullFace => &mut self.cull_state,
+            _ => self.capability_flags.entry(capability).or_default(),
+        }
+    }
+
+    // The bound vertex array object. Deleting it reverts the binding to zero, so it always exists.
+    pub(crate) fn vertex_array(&mut self) -> &mut VertexArray {
+        self.vertex_arrays.get_mut(&self.vertex_array_binding).unwrap()
+    }
+
+    // Buffer name bound to `target`. The element array buffer binding is part of the bound vertex array object.
+    pub(crate) fn buffer_binding(&self, target: BufferTarget) -> u32 {
+        match target {
+            BufferTarget::ElementArray => self.vertex_arrays[&self.vertex_array_binding].element_array_buffer,
+            _ => self.buffer_bindings.get(&target).copied().unwrap_or(0),
+        }
+    }
+
+    pub(crate) fn bind_buffer(&mut self, target: BufferTarget, buffer: u32) {
+        match target {
+            BufferTarget::ElementArray => self.vertex_array().element_array_buffer = buffer,
+            _ if buffer == 0 => {
+                self.buffer_bindings.remove(&target);
+            }
+            _ => {
+                self.buffer_bindings.insert(target, buffer);
+            }
+        }
+    }
+
+    // Whether a capability without dedicated state is enabled
+    pub(crate) fn is_flag_enabled(&self, capability: Capability) -> bool {
+        self.capability_flags.get(&capability).is_some_and(|flag| flag.enabled)
+    }
+
+    // Only the first error is recorded, following ones are discarded until glGetError resets the flag.
+    pub(crate) fn set_error(&mut self, error: ErrorCode) {
+        if self.error == ErrorCode::NoError {
+            self.error = error;
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
-struct Viewport {
-    x: i32,
-    y: i32,
-    width: i32,
-    height: i32,
+pub(crate) struct Viewport {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
 }
\ No newline at end of file
@@ -0,0 +1,583 @@
+use std::{array, collections::VecDeque, mem};
+
+use crate::{
+    context::{with_current_context, GlContext, Viewport},
+    enums::{BufferTarget, Capability, ErrorCode, Framebuffer, IndexType, PrimitiveMode, GL_MAX_DRAW_BUFFERS, GL_SUBPIXEL_BITS},
+    renderer::{check_framebuffer_complete, with_color_buffer, with_depth_plane, ColorSelect},
+    states::ColorMask,
+    types::{quantize_depth, sample_count, ColorData, GlSizei, Rect},
+    vertex::VertexFetch,
+};
+
+// Output of vertex processing. Without shader programs attribute 0 is passed through as the clip coordinates and
+// attribute 1 as the color of the fragments.
+#[derive(Debug, Clone, Copy)]
+struct ProcessedVertex {
+    position: [f64; 4],
+    color: ColorData,
+}
+
+fn process_vertex(fetch: &VertexFetch, vertex: usize) -> ProcessedVertex {
+    let inputs = fetch.fetch(vertex, 0);
+    ProcessedVertex { position: inputs[0].components(), color: inputs[1] }
+}
+
+// Groups a stream of vertices into triangles with the provoking vertex last. Only the vertices later triangles
+// still share are kept. Points and lines are not rasterized.
+struct TriangleAssembler<T> {
+    mode: PrimitiveMode,
+    // Shared first vertex of a triangle fan
+    first: Option<T>,
+    // Latest vertices of the primitive, at most as many as a triangle spans
+    window: VecDeque<T>,
+    // Vertices pushed since the primitive was started
+    count: usize,
+}
+
+impl<T: Copy> TriangleAssembler<T> {
+    fn new(mode: PrimitiveMode) -> Self {
+        Self { mode, first: None, window: VecDeque::with_capacity(6), count: 0 }
+    }
+
+    // Starts a new primitive, used by primitive restart
+    fn restart(&mut self) {
+        self.first = None;
+        self.window.clear();
+        self.count = 0;
+    }
+
+    // Adds the next vertex, returning the triangle it completes
+    fn push(&mut self, vertex: T) -> Option<[T; 3]> {
+        let index = self.count;
+        self.count += 1;
+        let span = match self.mode {
+            PrimitiveMode::Triangles | PrimitiveMode::TriangleStrip => 3,
+            PrimitiveMode::TriangleFan if self.first.is_none() => {
+                self.first = Some(vertex);
+                return None;
+            }
+            PrimitiveMode::TriangleFan => 2,
+            PrimitiveMode::TrianglesAdjacency => 6,
+            PrimitiveMode::TriangleStripAdjacency => 5,
+            _ => return None,
+        };
+        if self.window.len() == span {
+            // Lists start every triangle afresh while strips and fans slide along
+            match self.mode {
+                PrimitiveMode::Triangles | PrimitiveMode::TrianglesAdjacency => self.window.clear(),
+                _ => drop(self.window.pop_front()),
+            }
+        }
+        self.window.push_back(vertex);
+        if self.window.len() < span {
+            return None;
+        }
+        let window = &self.window;
+        match self.mode {
+            PrimitiveMode::Triangles => Some([window[0], window[1], window[2]]),
+            // Every other triangle is flipped so they all keep the winding of the first one
+            PrimitiveMode::TriangleStrip if index.is_multiple_of(2) => Some([window[0], window[1], window[2]]),
+            PrimitiveMode::TriangleStrip => Some([window[1], window[0], window[2]]),
+            PrimitiveMode::TriangleFan => Some([self.first?, window[0], window[1]]),
+            // Without a geometry shader the adjacent vertices are ignored
+            PrimitiveMode::TrianglesAdjacency => Some([window[0], window[2], window[4]]),
+            PrimitiveMode::TriangleStripAdjacency if index % 2 == 1 => None,
+            PrimitiveMode::TriangleStripAdjacency if index.is_multiple_of(4) => Some([window[0], window[2], window[4]]),
+            PrimitiveMode::TriangleStripAdjacency => Some([window[2], window[0], window[4]]),
+            _ => None,
+        }
+    }
+}
+
+// Vertex of a triangle being clipped, with its color widened so it can be interpolated
+#[derive(Debug, Clone, Copy)]
+struct ClipVertex {
+    position: [f64; 4],
+    color: [f64; 4],
+}
+
+impl ClipVertex {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Self {
+            position: array::from_fn(|index| self.position[index] + (other.position[index] - self.position[index]) * t),
+            color: array::from_fn(|index| self.color[index] + (other.color[index] - self.color[index]) * t),
+        }
+    }
+}
+
+// Clips a triangle against the view volume -w <= x, y, z <= w and returns the convex polygon left of it.
+// With depth clamping the near and far planes are not clipped against.
+fn clip_triangle(triangle: [ClipVertex; 3], depth_clamp: bool) -> Vec<ClipVertex> {
+    let mut polygon = triangle.to_vec();
+    let planes = if depth_clamp { 4 } else { 6 };
+    for plane in 0..planes {
+        // Distance to the plane, positive inside
+        let distance = |vertex: &ClipVertex| {
+            let (coordinate, w) = (vertex.position[plane / 2], vertex.position[3]);
+            if plane % 2 == 0 { w + coordinate } else { w - coordinate }
+        };
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (index, current) in polygon.iter().enumerate() {
+            let next = &polygon[(index + 1) % polygon.len()];
+            let (current_distance, next_distance) = (distance(current), distance(next));
+            if current_distance >= 0.0 {
+                clipped.push(*current);
+            }
+            if (current_distance >= 0.0) != (next_distance >= 0.0) {
+                clipped.push(current.lerp(next, current_distance / (current_distance - next_distance)));
+            }
+        }
+        polygon = clipped;
+    }
+    polygon
+}
+
+// Vertex in window coordinates. x and y are in fixed point with GL_SUBPIXEL_BITS fractional bits, the color is
+// divided by w for perspective correct interpolation.
+#[derive(Debug, Clone, Copy)]
+struct WindowVertex {
+    x: i64,
+    y: i64,
+    z: f64,
+    inv_w: f64,
+    color: [f64; 4],
+}
+
+fn to_window(vertex: &ClipVertex, viewport: &Viewport) -> WindowVertex {
+    let [x, y, z, w] = vertex.position;
+    let inv_w = 1.0 / w;
+    let scale = (1 << GL_SUBPIXEL_BITS) as f64;
+    let window_x = (x * inv_w + 1.0) * viewport.width as f64 / 2.0 + viewport.x as f64;
+    let window_y = (y * inv_w + 1.0) * viewport.height as f64 / 2.0 + viewport.y as f64;
+    WindowVertex {
+        x: (window_x * scale).round() as i64,
+        y: (window_y * scale).round() as i64,
+        // Depth range [0; 1]
+        z: ((z * inv_w + 1.0) / 2.0).clamp(0.0, 1.0),
+        inv_w,
+        color: vertex.color.map(|component| component * inv_w),
+    }
+}
+
+// Twice the signed area of the triangle `a`, `b`, (`x`, `y`), positive when it's counter-clockwise.
+// Clipped vertices lie within the viewport, whose size is limited by GL_MAX_VIEWPORT_DIMS, so this can't overflow.
+fn edge(a: &WindowVertex, b: &WindowVertex, x: i64, y: i64) -> i64 {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
+
+// Pixel covered by a triangle
+struct Fragment {
+    x: usize,
+    y: usize,
+    depth: f32,
+    color: ColorData,
+    // One bit per sample still written. Every sample of a pixel is covered when its center is.
+    coverage: u32,
+}
+
+// Appends a fragment for every pixel inside `bounds` whose center is covered by `triangle`. Pixel centers on an
+// edge are only covered by triangles to the right of or below it, so triangles sharing an edge never both cover them.
+// `flat` replaces the interpolated color.
+fn rasterize_triangle(triangle: [WindowVertex; 3], flat: Option<ColorData>, bounds: Rect, cull_back: bool, fragments: &mut Vec<Fragment>) {
+    let [v0, mut v1, mut v2] = triangle;
+    let mut area = edge(&v0, &v1, v2.x, v2.y);
+    // Front faces are counter-clockwise, and back faces are culled when culling is enabled
+    if area == 0 || area < 0 && cull_back {
+        return;
+    }
+    if area < 0 {
+        mem::swap(&mut v1, &mut v2);
+        area = -area;
+    }
+    let vertices = [v0, v1, v2];
+    // The edge opposite of each vertex. Going counter-clockwise, top edges run left and left edges run down.
+    let edges = [(v1, v2), (v2, v0), (v0, v1)];
+    let bias = edges.map(|(a, b)| if b.y < a.y || b.y == a.y && b.x < a.x { 0 } else { -1 });
+    let unit = 1i64 << GL_SUBPIXEL_BITS;
+    let half = unit / 2;
+    // Pixels whose centers are inside the bounding box of the triangle
+    let min_x = vertices.iter().map(|vertex| vertex.x).min().unwrap();
+    let max_x = vertices.iter().map(|vertex| vertex.x).max().unwrap();
+    let min_y = vertices.iter().map(|vertex| vertex.y).min().unwrap();
+    let max_y = vertices.iter().map(|vertex| vertex.y).max().unwrap();
+    let x0 = (min_x - half + unit - 1).div_euclid(unit).max(bounds.x0 as i64);
+    let x1 = ((max_x - half).div_euclid(unit) + 1).min(bounds.x1 as i64);
+    let y0 = (min_y - half + unit - 1).div_euclid(unit).max(bounds.y0 as i64);
+    let y1 = ((max_y - half).div_euclid(unit) + 1).min(bounds.y1 as i64);
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let (center_x, center_y) = (x * unit + half, y * unit + half);
+            let weights = edges.map(|(a, b)| edge(&a, &b, center_x, center_y));
+            if weights.iter().zip(bias).any(|(weight, bias)| weight + bias < 0) {
+                continue;
+            }
+            let barycentric = weights.map(|weight| weight as f64 / area as f64);
+            let interpolate = |value: fn(&WindowVertex) -> f64| {
+                barycentric.iter().zip(&vertices).map(|(weight, vertex)| weight * value(vertex)).sum::<f64>()
+            };
+            let depth = interpolate(|vertex| vertex.z);
+            let color = flat.unwrap_or_else(|| {
+                let inv_w = interpolate(|vertex| vertex.inv_w);
+                ColorData::Float(array::from_fn(|component| {
+                    let value: f64 = barycentric.iter().zip(&vertices).map(|(weight, vertex)| weight * vertex.color[component]).sum();
+                    (value / inv_w) as f32
+                }))
+            });
+            fragments.push(Fragment { x: x as usize, y: y as usize, depth: depth as f32, color, coverage: u32::MAX });
+        }
+    }
+}
+
+// Runs the depth test on `fragments` and writes the remaining ones to every active draw buffer of `framebuffer`.
+// The stencil test and blending only have their initial state, where they leave fragments unchanged.
+fn write_fragments(context: &mut GlContext, framebuffer: Framebuffer, fragments: &mut Vec<Fragment>) {
+    let depth_state = context.depth_state;
+    // Without a depth buffer the depth test always passes
+    if depth_state.enabled {
+        with_depth_plane(context, framebuffer, |plane, bits| {
+            let count = sample_count(plane.samples);
+            for fragment in fragments.iter_mut() {
+                // Compared and stored at the precision of the depth buffer
+                fragment.depth = quantize_depth(fragment.depth, bits);
+                let first = (fragment.y * plane.width + fragment.x) * count;
+                for (sample, stored) in plane.pixels[first..first + count].iter_mut().enumerate() {
+                    // GL_LESS, the initial depth function
+                    if fragment.depth < *stored {
+                        if depth_state.write_mask {
+                            *stored = fragment.depth;
+                        }
+                    } else {
+                        fragment.coverage &= !(1 << sample);
+                    }
+                }
+            }
+            fragments.retain(|fragment| fragment.coverage != 0);
+        });
+    }
+    let framebuffer_srgb = context.is_flag_enabled(Capability::FramebufferSrgb);
+    for draw_buffer in 0..GL_MAX_DRAW_BUFFERS {
+        let color_mask = context.color_masks[draw_buffer];
+        with_color_buffer(context, framebuffer, ColorSelect::Draw(draw_buffer), |buffer| {
+            let count = sample_count(buffer.samples);
+            for fragment in fragments.iter() {
+                let value = buffer.srgb_encoded(fragment.color, framebuffer_srgb);
+                let first = (fragment.y * buffer.width + fragment.x) * count;
+                for sample in (0..count).filter(|sample| fragment.coverage & 1 << sample != 0) {
+                    let value = if color_mask == ColorMask::default() {
+                        value
+                    } else {
+                        color_mask.apply(buffer.get(first + sample), value)
+                    };
+                    buffer.set(first + sample, value);
+                }
+            }
+        });
+    }
+}
+
+// Runs the pipeline over `vertices` of the bound vertex array object. None restarts the primitive.
+// Vertices are processed as they are assembled so only those of the current triangles are kept.
+fn draw(context: &mut GlContext, mode: PrimitiveMode, fetch: &VertexFetch, vertices: impl Iterator<Item = Option<usize>>) {
+    if context.is_flag_enabled(Capability::RasterizerDiscard) {
+        return;
+    }
+    let framebuffer = context.framebuffer_state.write_framebuffer;
+    let (width, height) = match framebuffer {
+        Framebuffer::Default => (context.default_framebuffer.width, context.default_framebuffer.height),
+        Framebuffer::UserDefined(fbo_id) => context.framebuffer_objects[&fbo_id].size(),
+    };
+    let bounds = context.scissor.clip(width, height);
+    let viewport = context.viewport;
+    // The initial cull face mode culls back faces
+    let cull_back = context.cull_state.enabled;
+    let depth_clamp = context.is_flag_enabled(Capability::DepthClamp);
+    let mut fragments = Vec::new();
+    let mut assembler = TriangleAssembler::new(mode);
+    for vertex in vertices {
+        let Some(vertex) = vertex else {
+            assembler.restart();
+            continue;
+        };
+        let Some(triangle) = assembler.push(process_vertex(fetch, vertex)) else {
+            continue;
+        };
+        // Integer colors can't be interpolated and are taken from the provoking vertex
+        let flat = match triangle[2].color {
+            ColorData::Float(_) => None,
+            color => Some(color),
+        };
+        let polygon = clip_triangle(
+            triangle.map(|vertex| ClipVertex { position: vertex.position, color: vertex.color.components() }),
+            depth_clamp,
+        );
+        // Only degenerate triangles through the eye are left with vertices at w = 0
+        if polygon.iter().any(|vertex| vertex.position[3] <= 0.0) {
+            continue;
+        }
+        let polygon: Vec<WindowVertex> = polygon.iter().map(|vertex| to_window(vertex, &viewport)).collect();
+        // The clipped polygon is convex and rasterized as a fan
+        for index in 1..polygon.len().saturating_sub(1) {
+            rasterize_triangle([polygon[0], polygon[index], polygon[index + 1]], flat, bounds, cull_back, &mut fragments);
+        }
+        // Fragments are written triangle by triangle so later triangles are depth tested against earlier ones
+        write_fragments(context, framebuffer, &mut fragments);
+        fragments.clear();
+    }
+}
+
+// Validation shared by the draw calls. Returns the primitive mode and the vertex fetch of the bound vertex array
+// object if anything should be drawn.
+fn begin_draw(context: &mut GlContext, mode: u32, count: GlSizei) -> Option<(PrimitiveMode, VertexFetch)> {
+    let Some(mode) = PrimitiveMode::from_u32(mode) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    };
+    if count < 0 {
+        context.set_error(ErrorCode::InvalidValue);
+        return None;
+    }
+    let fetch = VertexFetch::new(context);
+    // Patches need a tessellation program, and mapped buffers can't be read from
+    if mode == PrimitiveMode::Patches || fetch.is_mapped_exclusively() {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    }
+    let framebuffer = context.framebuffer_state.write_framebuffer;
+    if !check_framebuffer_complete(context, framebuffer) {
+        return None;
+    }
+    Some((mode, fetch))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDrawArrays(mode: u32, first: i32, count: GlSizei) {
+    with_current_context(|context| {
+        if first < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let Some((mode, fetch)) = begin_draw(context, mode, count) else {
+            return;
+        };
+        draw(context, mode, &fetch, (first as usize..first as usize + count as usize).map(Some));
+    });
+}
+
+// Reads `count` indices from the element array buffer starting `offset` bytes into it. Indices past the end of
+// the buffer are dropped. With GL_PRIMITIVE_RESTART_FIXED_INDEX the largest index of the type reads as None.
+fn read_indices(context: &mut GlContext, index_type: IndexType, count: usize, offset: usize) -> Option<Vec<Option<usize>>> {
+    let shared_state = context.shared.clone();
+    let shared = shared_state.lock().unwrap();
+    // Client memory index arrays are not supported
+    let Some(buffer) = shared.buffers.objects.get(&context.buffer_binding(BufferTarget::ElementArray)) else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    };
+    let buffer = buffer.lock().unwrap();
+    if buffer.is_mapped_exclusively() {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    }
+    let restart = context.is_flag_enabled(Capability::PrimitiveRestartFixedIndex);
+    let size = index_type.size();
+    let restart_index = u32::MAX >> (32 - size * 8);
+    let indices = buffer
+        .data
+        .get(offset..)
+        .unwrap_or_default()
+        .chunks_exact(size)
+        .take(count)
+        .map(|bytes| {
+            let index = match index_type {
+                IndexType::Byte => bytes[0] as u32,
+                IndexType::Short => u16::from_ne_bytes([bytes[0], bytes[1]]) as u32,
+                IndexType::Int => u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            };
+            (!restart || index != restart_index).then_some(index as usize)
+        })
+        .collect();
+    Some(indices)
+}
+
+// `indices` is an offset into the buffer bound to GL_ELEMENT_ARRAY_BUFFER
+#[unsafe(no_mangle)]
+pub extern "C" fn glDrawElements(mode: u32, count: GlSizei, index_type: u32, indices: *const u8) {
+    with_current_context(|context| {
+        let Some(index_type) = IndexType::from_u32(index_type) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let Some((mode, fetch)) = begin_draw(context, mode, count) else {
+            return;
+        };
+        let Some(vertices) = read_indices(context, index_type, count as usize, indices as usize) else {
+            return;
+        };
+        draw(context, mode, &fetch, vertices.into_iter());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip_vertex(x: f64, y: f64, z: f64, w: f64) -> ClipVertex {
+        ClipVertex { position: [x, y, z, w], color: [x, y, z, 1.0] }
+    }
+
+    // Vertex at window position (`x`, `y`) in pixels
+    fn window_vertex(x: f64, y: f64) -> WindowVertex {
+        let scale = (1 << GL_SUBPIXEL_BITS) as f64;
+        WindowVertex { x: (x * scale) as i64, y: (y * scale) as i64, z: 0.5, inv_w: 1.0, color: [1.0, 0.5, 0.25, 1.0] }
+    }
+
+    fn rasterize(triangle: [WindowVertex; 3], cull_back: bool) -> Vec<(usize, usize)> {
+        let mut fragments = Vec::new();
+        let bounds = Rect { x0: 0, y0: 0, x1: 16, y1: 16 };
+        rasterize_triangle(triangle, None, bounds, cull_back, &mut fragments);
+        fragments.iter().map(|fragment| (fragment.x, fragment.y)).collect()
+    }
+
+    // Triangles assembled from `vertices`, where None restarts the primitive
+    fn assemble(mode: PrimitiveMode, vertices: &[Option<usize>]) -> Vec<[usize; 3]> {
+        let mut assembler = TriangleAssembler::new(mode);
+        vertices
+            .iter()
+            .filter_map(|&vertex| match vertex {
+                Some(vertex) => assembler.push(vertex),
+                None => {
+                    assembler.restart();
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn assemble_triangles(mode: PrimitiveMode, count: usize) -> Vec<[usize; 3]> {
+        assemble(mode, &(0..count).map(Some).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn assembles_triangles() {
+        assert_eq!(assemble_triangles(PrimitiveMode::Triangles, 7), vec![[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(assemble_triangles(PrimitiveMode::TriangleStrip, 5), vec![[0, 1, 2], [2, 1, 3], [2, 3, 4]]);
+        assert_eq!(assemble_triangles(PrimitiveMode::TriangleFan, 5), vec![[0, 1, 2], [0, 2, 3], [0, 3, 4]]);
+        assert_eq!(assemble_triangles(PrimitiveMode::TrianglesAdjacency, 12), vec![[0, 2, 4], [6, 8, 10]]);
+        assert_eq!(assemble_triangles(PrimitiveMode::TriangleStripAdjacency, 8), vec![[0, 2, 4], [4, 2, 6]]);
+        assert_eq!(assemble_triangles(PrimitiveMode::TriangleStripAdjacency, 10), vec![[0, 2, 4], [4, 2, 6], [4, 6, 8]]);
+        assert!(assemble_triangles(PrimitiveMode::TriangleStrip, 2).is_empty());
+        assert!(assemble_triangles(PrimitiveMode::TriangleFan, 1).is_empty());
+        assert!(assemble_triangles(PrimitiveMode::Lines, 6).is_empty());
+    }
+
+    #[test]
+    fn restarts_primitives() {
+        let vertices = [Some(0), Some(1), Some(2), Some(3), None, Some(4), Some(5), Some(6), Some(7)];
+        assert_eq!(assemble(PrimitiveMode::TriangleStrip, &vertices), vec![[0, 1, 2], [2, 1, 3], [4, 5, 6], [6, 5, 7]]);
+        assert_eq!(assemble(PrimitiveMode::TriangleFan, &vertices), vec![[0, 1, 2], [0, 2, 3], [4, 5, 6], [4, 6, 7]]);
+        assert_eq!(assemble(PrimitiveMode::Triangles, &vertices), vec![[0, 1, 2], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn keeps_triangle_inside_view_volume() {
+        let triangle = [clip_vertex(-0.5, -0.5, 0.0, 1.0), clip_vertex(0.5, -0.5, 0.0, 1.0), clip_vertex(0.0, 0.5, 0.0, 1.0)];
+        let polygon = clip_triangle(triangle, false);
+        assert_eq!(polygon.len(), 3);
+        for (clipped, original) in polygon.iter().zip(&triangle) {
+            assert_eq!(clipped.position, original.position);
+        }
+    }
+
+    #[test]
+    fn clips_triangle_against_right_plane() {
+        let triangle = [clip_vertex(0.0, 0.0, 0.0, 1.0), clip_vertex(2.0, 0.0, 0.0, 1.0), clip_vertex(0.0, 1.0, 0.0, 1.0)];
+        let polygon = clip_triangle(triangle, false);
+        let positions: Vec<_> = polygon.iter().map(|vertex| [vertex.position[0], vertex.position[1]]).collect();
+        assert_eq!(positions, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 0.5], [0.0, 1.0]]);
+        // Colors are interpolated along with the position
+        assert_eq!(polygon[2].color, [1.0, 0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn clips_near_plane_unless_depth_clamped() {
+        let triangle = [clip_vertex(-0.5, -0.5, -2.0, 1.0), clip_vertex(0.5, -0.5, 0.0, 1.0), clip_vertex(0.0, 0.5, 0.0, 1.0)];
+        let clipped = clip_triangle(triangle, false);
+        assert_eq!(clipped.len(), 4);
+        assert!(clipped.iter().all(|vertex| vertex.position[2] >= -vertex.position[3]));
+        assert_eq!(clip_triangle(triangle, true).len(), 3);
+    }
+
+    #[test]
+    fn discards_triangle_outside_view_volume() {
+        let triangle = [clip_vertex(2.0, 0.0, 0.0, 1.0), clip_vertex(3.0, 0.0, 0.0, 1.0), clip_vertex(2.0, 1.0, 0.0, 1.0)];
+        assert!(clip_triangle(triangle, false).is_empty());
+    }
+
+    #[test]
+    fn maps_clip_coordinates_to_window() {
+        let viewport = Viewport { x: 10, y: 20, width: 100, height: 50 };
+        let vertex = to_window(&clip_vertex(0.5, -0.5, 0.5, 2.0), &viewport);
+        let scale = 1 << GL_SUBPIXEL_BITS;
+        // NDC (0.25, -0.25, 0.25) lands at window (72.5, 38.75)
+        assert_eq!(vertex.x, 145 * scale / 2);
+        assert_eq!(vertex.y, 155 * scale / 4);
+        assert_eq!(vertex.z, 0.625);
+        assert_eq!(vertex.inv_w, 0.5);
+        assert_eq!(vertex.color, [0.25, -0.25, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn clamps_window_depth() {
+        let viewport = Viewport { x: 0, y: 0, width: 8, height: 8 };
+        assert_eq!(to_window(&clip_vertex(0.0, 0.0, -2.0, 1.0), &viewport).z, 0.0);
+        assert_eq!(to_window(&clip_vertex(0.0, 0.0, 2.0, 1.0), &viewport).z, 1.0);
+    }
+
+    #[test]
+    fn covers_shared_edge_once() {
+        // A square whose corners and diagonal lie on pixel centers, split along the diagonal
+        let (bottom_left, bottom_right) = (window_vertex(0.5, 0.5), window_vertex(4.5, 0.5));
+        let (top_right, top_left) = (window_vertex(4.5, 4.5), window_vertex(0.5, 4.5));
+        let lower = rasterize([bottom_left, bottom_right, top_right], false);
+        let upper = rasterize([bottom_left, top_right, top_left], false);
+        let mut covered = [[0; 4]; 5];
+        for &(x, y) in lower.iter().chain(&upper) {
+            covered[y][x] += 1;
+        }
+        // Centers on the left and top edges are covered, the ones on the right and bottom edges are not
+        assert_eq!(covered[0], [0; 4]);
+        for row in &covered[1..] {
+            assert_eq!(*row, [1; 4]);
+        }
+        // The diagonal belongs to the triangle to its right
+        assert!(lower.contains(&(1, 1)) && !upper.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn culls_clockwise_triangles() {
+        let triangle = [window_vertex(0.0, 0.0), window_vertex(0.0, 4.0), window_vertex(4.0, 0.0)];
+        assert!(rasterize(triangle, true).is_empty());
+        let mut expected = rasterize([triangle[0], triangle[2], triangle[1]], false);
+        let mut fragments = rasterize(triangle, false);
+        expected.sort();
+        fragments.sort();
+        assert_eq!(fragments, expected);
+        // Centers on the hypotenuse are on a right edge and left out
+        assert_eq!(fragments.len(), 6);
+    }
+
+    #[test]
+    fn skips_degenerate_triangles() {
+        assert!(rasterize([window_vertex(0.0, 0.0), window_vertex(2.0, 2.0), window_vertex(4.0, 4.0)], false).is_empty());
+    }
+
+    #[test]
+    fn uses_flat_color() {
+        let triangle = [window_vertex(0.0, 0.0), window_vertex(4.0, 0.0), window_vertex(0.0, 4.0)];
+        let mut fragments = Vec::new();
+        let bounds = Rect { x0: 0, y0: 0, x1: 16, y1: 16 };
+        rasterize_triangle(triangle, Some(ColorData::Int([1, 2, 3, 4])), bounds, false, &mut fragments);
+        assert!(!fragments.is_empty());
+        assert!(fragments.iter().all(|fragment| matches!(fragment.color, ColorData::Int([1, 2, 3, 4]))));
+    }
+}
@@ -1,4 +1,99 @@
 pub static GL_MAX_COLOR_ATTACHMENTS: usize = 16;
+pub static GL_MAX_DRAW_BUFFERS: usize = GL_MAX_COLOR_ATTACHMENTS;
+pub static GL_MAX_VIEWPORT_DIMS: i32 = 16384;
+pub static GL_MAX_RENDERBUFFER_SIZE: i32 = 16384;
+pub static GL_MAX_TEXTURE_SIZE: i32 = 16384;
+pub static GL_MAX_3D_TEXTURE_SIZE: i32 = 2048;
+pub static GL_MAX_ARRAY_TEXTURE_LAYERS: i32 = 2048;
+// Limits of the defaults of framebuffers without attachments
+pub static GL_MAX_FRAMEBUFFER_WIDTH: i32 = 16384;
+pub static GL_MAX_FRAMEBUFFER_HEIGHT: i32 = 16384;
+pub static GL_MAX_FRAMEBUFFER_LAYERS: i32 = 2048;
+pub static GL_MAX_FRAMEBUFFER_SAMPLES: i32 = GL_MAX_SAMPLES;
+// Sample counts of multisampled renderbuffers, textures and default framebuffers
+pub static GL_MAX_SAMPLES: i32 = 16;
+pub static GL_MAX_COLOR_TEXTURE_SAMPLES: i32 = GL_MAX_SAMPLES;
+pub static GL_MAX_DEPTH_TEXTURE_SAMPLES: i32 = GL_MAX_SAMPLES;
+pub static GL_MAX_INTEGER_SAMPLES: i32 = GL_MAX_SAMPLES;
+// Binding points of the indexed buffer targets
+pub static GL_MAX_UNIFORM_BUFFER_BINDINGS: u32 = 84;
+pub static GL_MAX_TRANSFORM_FEEDBACK_BUFFERS: u32 = 4;
+pub static GL_MAX_SHADER_STORAGE_BUFFER_BINDINGS: u32 = 8;
+pub static GL_MAX_ATOMIC_COUNTER_BUFFER_BINDINGS: u32 = 8;
+// Buffers live in system memory, so ranges only have to be word aligned
+pub static GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT: usize = 4;
+pub static GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT: usize = 4;
+pub static GL_MAX_VERTEX_ATTRIBS: usize = 16;
+pub static GL_MAX_VERTEX_ATTRIB_STRIDE: i32 = 2048;
+pub static GL_MAX_VERTEX_ATTRIB_BINDINGS: usize = 16;
+pub static GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET: i32 = 2047;
+// Window coordinates are snapped to 1/16th of a pixel before rasterization
+pub static GL_SUBPIXEL_BITS: u32 = 4;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PrimitiveMode {
+    Points = 0x0000,
+    Lines = 0x0001,
+    LineLoop = 0x0002,
+    LineStrip = 0x0003,
+    Triangles = 0x0004,
+    TriangleStrip = 0x0005,
+    TriangleFan = 0x0006,
+    LinesAdjacency = 0x000a,
+    LineStripAdjacency = 0x000b,
+    TrianglesAdjacency = 0x000c,
+    TriangleStripAdjacency = 0x000d,
+    Patches = 0x000e,
+}
+
+impl PrimitiveMode {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Points as u32 == n => Some(Self::Points),
+            n if Self::Lines as u32 == n => Some(Self::Lines),
+            n if Self::LineLoop as u32 == n => Some(Self::LineLoop),
+            n if Self::LineStrip as u32 == n => Some(Self::LineStrip),
+            n if Self::Triangles as u32 == n => Some(Self::Triangles),
+            n if Self::TriangleStrip as u32 == n => Some(Self::TriangleStrip),
+            n if Self::TriangleFan as u32 == n => Some(Self::TriangleFan),
+            n if Self::LinesAdjacency as u32 == n => Some(Self::LinesAdjacency),
+            n if Self::LineStripAdjacency as u32 == n => Some(Self::LineStripAdjacency),
+            n if Self::TrianglesAdjacency as u32 == n => Some(Self::TrianglesAdjacency),
+            n if Self::TriangleStripAdjacency as u32 == n => Some(Self::TriangleStripAdjacency),
+            n if Self::Patches as u32 == n => Some(Self::Patches),
+            _ => None,
+        }
+    }
+}
+
+// GL_UNSIGNED_* types of the indices read by glDrawElements
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum IndexType {
+    Byte = 0x1401,
+    Short = 0x1403,
+    Int = 0x1405,
+}
+
+impl IndexType {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Byte as u32 == n => Some(Self::Byte),
+            n if Self::Short as u32 == n => Some(Self::Short),
+            n if Self::Int as u32 == n => Some(Self::Int),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        match self {
+            Self::Byte => 1,
+            Self::Short => 2,
+            Self::Int => 4,
+        }
+    }
+}
 
 #[repr(u32)]
 pub enum ClearBufferMask {
@@ -7,6 +102,1104 @@ pub enum ClearBufferMask {
     STENCIL = 0x400,
 }
 
+// Filters of glBlitFramebuffer
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Filter {
+    Nearest = 0x2600,
+    Linear = 0x2601,
+}
+
+impl Filter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Nearest as u32 == n => Some(Self::Nearest),
+            n if Self::Linear as u32 == n => Some(Self::Linear),
+            _ => None,
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ErrorCode {
+    NoError = 0x0,
+    InvalidEnum = 0x500,
+    InvalidValue = 0x501,
+    InvalidOperation = 0x502,
+    // There are no matrix or debug group stacks, so GL_STACK_OVERFLOW and GL_STACK_UNDERFLOW are never raised
+    OutOfMemory = 0x505,
+    InvalidFramebufferOperation = 0x506,
+}
+
+// Errors of the KoriExt window system layer. Values follow their EGL counterparts.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KoriError {
+    Success = 0x3000,
+    BadAccess = 0x3002,
+    BadContext = 0x3006,
+    BadAttribute = 0x3004,
+    BadMatch = 0x3009,
+    BadParameter = 0x300c,
+}
+
+// Keys of a glKCreateContextAttribs attribute list. Values follow their EGL counterparts.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KoriAttrib {
+    AlphaSize = 0x3021,
+    BlueSize = 0x3022,
+    GreenSize = 0x3023,
+    RedSize = 0x3024,
+    DepthSize = 0x3025,
+    StencilSize = 0x3026,
+    Samples = 0x3031,
+    None = 0x3038,
+    RenderBuffer = 0x3086,
+    ColorSpace = 0x309d,
+    ColorComponentType = 0x3339,
+}
+
+impl KoriAttrib {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::AlphaSize as u32 == n => Some(Self::AlphaSize),
+            n if Self::BlueSize as u32 == n => Some(Self::BlueSize),
+            n if Self::GreenSize as u32 == n => Some(Self::GreenSize),
+            n if Self::RedSize as u32 == n => Some(Self::RedSize),
+            n if Self::DepthSize as u32 == n => Some(Self::DepthSize),
+            n if Self::StencilSize as u32 == n => Some(Self::StencilSize),
+            n if Self::Samples as u32 == n => Some(Self::Samples),
+            n if Self::None as u32 == n => Some(Self::None),
+            n if Self::RenderBuffer as u32 == n => Some(Self::RenderBuffer),
+            n if Self::ColorSpace as u32 == n => Some(Self::ColorSpace),
+            n if Self::ColorComponentType as u32 == n => Some(Self::ColorComponentType),
+            _ => None,
+        }
+    }
+}
+
+// Values of KoriAttrib::RenderBuffer
+pub const KORI_BACK_BUFFER: i32 = 0x3084;
+pub const KORI_SINGLE_BUFFER: i32 = 0x3085;
+// Values of KoriAttrib::ColorSpace
+pub const KORI_COLORSPACE_SRGB: i32 = 0x3089;
+pub const KORI_COLORSPACE_LINEAR: i32 = 0x308a;
+// Values of KoriAttrib::ColorComponentType
+pub const KORI_COLOR_COMPONENT_TYPE_FIXED: i32 = 0x333a;
+pub const KORI_COLOR_COMPONENT_TYPE_FLOAT: i32 = 0x333b;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum InternalFormat {
+    R8 = 0x8229,
+    Rg8 = 0x822b,
+    Rgb8 = 0x8051,
+    Rgba8 = 0x8058,
+    Srgb8 = 0x8c41,
+    Srgb8Alpha8 = 0x8c43,
+    Rgb10A2 = 0x8059,
+    Rgb565 = 0x8d62,
+    R16 = 0x822a,
+    Rg16 = 0x822c,
+    Rgba16 = 0x805b,
+    R16F = 0x822d,
+    Rg16F = 0x822f,
+    Rgb16F = 0x881b,
+    Rgba16F = 0x881a,
+    R32F = 0x822e,
+    Rg32F = 0x8230,
+    Rgb32F = 0x8815,
+    Rgba32F = 0x8814,
+    R11FG11FB10F = 0x8c3a,
+    R8I = 0x8231,
+    R8UI = 0x8232,
+    R16I = 0x8233,
+    R16UI = 0x8234,
+    R32I = 0x8235,
+    R32UI = 0x8236,
+    Rg8I = 0x8237,
+    Rg8UI = 0x8238,
+    Rg16I = 0x8239,
+    Rg16UI = 0x823a,
+    Rg32I = 0x823b,
+    Rg32UI = 0x823c,
+    Rgba8I = 0x8d8e,
+    Rgba8UI = 0x8d7c,
+    Rgba16I = 0x8d88,
+    Rgba16UI = 0x8d76,
+    Rgba32I = 0x8d82,
+    Rgba32UI = 0x8d70,
+    Rgb10A2UI = 0x906f,
+    DepthComponent16 = 0x81a5,
+    DepthComponent24 = 0x81a6,
+    DepthComponent32F = 0x8cac,
+    StencilIndex8 = 0x8d48,
+    Depth24Stencil8 = 0x88f0,
+    Depth32FStencil8 = 0x8cad,
+}
+
+// Representation of the components of an internal format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ComponentType {
+    UnsignedNormalized,
+    Float,
+    Int,
+    UnsignedInt,
+}
+
+impl ComponentType {
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(self, Self::Int | Self::UnsignedInt)
+    }
+}
+
+// Which kind of buffer an internal format is stored in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FormatKind {
+    Color,
+    Depth,
+    Stencil,
+    DepthStencil,
+}
+
+impl InternalFormat {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::R8 as u32 == n => Some(Self::R8),
+            n if Self::Rg8 as u32 == n => Some(Self::Rg8),
+            n if Self::Rgb8 as u32 == n => Some(Self::Rgb8),
+            n if Self::Rgba8 as u32 == n => Some(Self::Rgba8),
+            n if Self::Srgb8 as u32 == n => Some(Self::Srgb8),
+            n if Self::Srgb8Alpha8 as u32 == n => Some(Self::Srgb8Alpha8),
+            n if Self::Rgb10A2 as u32 == n => Some(Self::Rgb10A2),
+            n if Self::Rgb565 as u32 == n => Some(Self::Rgb565),
+            n if Self::R16 as u32 == n => Some(Self::R16),
+            n if Self::Rg16 as u32 == n => Some(Self::Rg16),
+            n if Self::Rgba16 as u32 == n => Some(Self::Rgba16),
+            n if Self::R16F as u32 == n => Some(Self::R16F),
+            n if Self::Rg16F as u32 == n => Some(Self::Rg16F),
+            n if Self::Rgb16F as u32 == n => Some(Self::Rgb16F),
+            n if Self::Rgba16F as u32 == n => Some(Self::Rgba16F),
+            n if Self::R32F as u32 == n => Some(Self::R32F),
+            n if Self::Rg32F as u32 == n => Some(Self::Rg32F),
+            n if Self::Rgb32F as u32 == n => Some(Self::Rgb32F),
+            n if Self::Rgba32F as u32 == n => Some(Self::Rgba32F),
+            n if Self::R11FG11FB10F as u32 == n => Some(Self::R11FG11FB10F),
+            n if Self::R8I as u32 == n => Some(Self::R8I),
+            n if Self::R8UI as u32 == n => Some(Self::R8UI),
+            n if Self::R16I as u32 == n => Some(Self::R16I),
+            n if Self::R16UI as u32 == n => Some(Self::R16UI),
+            n if Self::R32I as u32 == n => Some(Self::R32I),
+            n if Self::R32UI as u32 == n => Some(Self::R32UI),
+            n if Self::Rg8I as u32 == n => Some(Self::Rg8I),
+            n if Self::Rg8UI as u32 == n => Some(Self::Rg8UI),
+            n if Self::Rg16I as u32 == n => Some(Self::Rg16I),
+            n if Self::Rg16UI as u32 == n => Some(Self::Rg16UI),
+            n if Self::Rg32I as u32 == n => Some(Self::Rg32I),
+            n if Self::Rg32UI as u32 == n => Some(Self::Rg32UI),
+            n if Self::Rgba8I as u32 == n => Some(Self::Rgba8I),
+            n if Self::Rgba8UI as u32 == n => Some(Self::Rgba8UI),
+            n if Self::Rgba16I as u32 == n => Some(Self::Rgba16I),
+            n if Self::Rgba16UI as u32 == n => Some(Self::Rgba16UI),
+            n if Self::Rgba32I as u32 == n => Some(Self::Rgba32I),
+            n if Self::Rgba32UI as u32 == n => Some(Self::Rgba32UI),
+            n if Self::Rgb10A2UI as u32 == n => Some(Self::Rgb10A2UI),
+            n if Self::DepthComponent16 as u32 == n => Some(Self::DepthComponent16),
+            n if Self::DepthComponent24 as u32 == n => Some(Self::DepthComponent24),
+            n if Self::DepthComponent32F as u32 == n => Some(Self::DepthComponent32F),
+            n if Self::StencilIndex8 as u32 == n => Some(Self::StencilIndex8),
+            n if Self::Depth24Stencil8 as u32 == n => Some(Self::Depth24Stencil8),
+            n if Self::Depth32FStencil8 as u32 == n => Some(Self::Depth32FStencil8),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn kind(&self) -> FormatKind {
+        match self {
+            Self::DepthComponent16 | Self::DepthComponent24 | Self::DepthComponent32F => FormatKind::Depth,
+            Self::StencilIndex8 => FormatKind::Stencil,
+            Self::Depth24Stencil8 | Self::Depth32FStencil8 => FormatKind::DepthStencil,
+            _ => FormatKind::Color,
+        }
+    }
+
+    // How the components of a format are stored. Depth values count as normalized or float, stencil indices
+    // as unsigned integers.
+    pub(crate) fn component_type(&self) -> ComponentType {
+        match self {
+            Self::R16F
+            | Self::Rg16F
+            | Self::Rgb16F
+            | Self::Rgba16F
+            | Self::R32F
+            | Self::Rg32F
+            | Self::Rgb32F
+            | Self::Rgba32F
+            | Self::R11FG11FB10F
+            | Self::DepthComponent32F
+            | Self::Depth32FStencil8 => ComponentType::Float,
+            Self::R8I | Self::R16I | Self::R32I | Self::Rg8I | Self::Rg16I | Self::Rg32I | Self::Rgba8I | Self::Rgba16I | Self::Rgba32I => {
+                ComponentType::Int
+            }
+            Self::R8UI
+            | Self::R16UI
+            | Self::R32UI
+            | Self::Rg8UI
+            | Self::Rg16UI
+            | Self::Rg32UI
+            | Self::Rgba8UI
+            | Self::Rgba16UI
+            | Self::Rgba32UI
+            | Self::Rgb10A2UI
+            | Self::StencilIndex8 => ComponentType::UnsignedInt,
+            _ => ComponentType::UnsignedNormalized,
+        }
+    }
+
+    // Formats storing red, green and blue in the non-linear sRGB color space
+    pub(crate) fn is_srgb(&self) -> bool {
+        matches!(self, Self::Srgb8 | Self::Srgb8Alpha8)
+    }
+
+    // Bytes of a single texel of a color format
+    pub(crate) fn texel_size(&self) -> usize {
+        let [red, green, blue, alpha, ..] = self.component_sizes();
+        (red + green + blue + alpha).div_ceil(8) as usize
+    }
+
+    // Bits of the red, green, blue, alpha, depth and stencil components
+    pub(crate) fn component_sizes(&self) -> [u32; 6] {
+        match self {
+            Self::R8 => [8, 0, 0, 0, 0, 0],
+            Self::Rg8 => [8, 8, 0, 0, 0, 0],
+            Self::Rgb8 | Self::Srgb8 => [8, 8, 8, 0, 0, 0],
+            Self::Rgba8 | Self::Srgb8Alpha8 => [8, 8, 8, 8, 0, 0],
+            Self::Rgb10A2 => [10, 10, 10, 2, 0, 0],
+            Self::Rgb565 => [5, 6, 5, 0, 0, 0],
+            Self::R16 | Self::R16F | Self::R16I | Self::R16UI => [16, 0, 0, 0, 0, 0],
+            Self::Rg16 | Self::Rg16F | Self::Rg16I | Self::Rg16UI => [16, 16, 0, 0, 0, 0],
+            Self::Rgb16F => [16, 16, 16, 0, 0, 0],
+            Self::Rgba16 | Self::Rgba16F | Self::Rgba16I | Self::Rgba16UI => [16, 16, 16, 16, 0, 0],
+            Self::R32F | Self::R32I | Self::R32UI => [32, 0, 0, 0, 0, 0],
+            Self::Rg32F | Self::Rg32I | Self::Rg32UI => [32, 32, 0, 0, 0, 0],
+            Self::Rgb32F => [32, 32, 32, 0, 0, 0],
+            Self::Rgba32F | Self::Rgba32I | Self::Rgba32UI => [32, 32, 32, 32, 0, 0],
+            Self::R11FG11FB10F => [11, 11, 10, 0, 0, 0],
+            Self::R8I | Self::R8UI => [8, 0, 0, 0, 0, 0],
+            Self::Rg8I | Self::Rg8UI => [8, 8, 0, 0, 0, 0],
+            Self::Rgba8I | Self::Rgba8UI => [8, 8, 8, 8, 0, 0],
+            Self::Rgb10A2UI => [10, 10, 10, 2, 0, 0],
+            Self::DepthComponent16 => [0, 0, 0, 0, 16, 0],
+            Self::DepthComponent24 => [0, 0, 0, 0, 24, 0],
+            Self::DepthComponent32F => [0, 0, 0, 0, 32, 0],
+            Self::StencilIndex8 => [0, 0, 0, 0, 0, 8],
+            Self::Depth24Stencil8 => [0, 0, 0, 0, 24, 8],
+            Self::Depth32FStencil8 => [0, 0, 0, 0, 32, 8],
+        }
+    }
+
+    // Bits of the depth component, 0 for formats without one
+    pub(crate) fn depth_bits(&self) -> u32 {
+        self.component_sizes()[4]
+    }
+
+    // Bits of the stencil component, 0 for formats without one
+    pub(crate) fn stencil_bits(&self) -> u32 {
+        self.component_sizes()[5]
+    }
+}
+
+pub static GL_RENDERBUFFER: u32 = 0x8d41;
+// Internal format reported for renderbuffers without storage
+pub static GL_RGBA: u32 = 0x1908;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RenderbufferParameter {
+    Samples = 0x8cab,
+    Width = 0x8d42,
+    Height = 0x8d43,
+    InternalFormat = 0x8d44,
+    RedSize = 0x8d50,
+    GreenSize = 0x8d51,
+    BlueSize = 0x8d52,
+    AlphaSize = 0x8d53,
+    DepthSize = 0x8d54,
+    StencilSize = 0x8d55,
+}
+
+impl RenderbufferParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Samples as u32 == n => Some(Self::Samples),
+            n if Self::Width as u32 == n => Some(Self::Width),
+            n if Self::Height as u32 == n => Some(Self::Height),
+            n if Self::InternalFormat as u32 == n => Some(Self::InternalFormat),
+            n if Self::RedSize as u32 == n => Some(Self::RedSize),
+            n if Self::GreenSize as u32 == n => Some(Self::GreenSize),
+            n if Self::BlueSize as u32 == n => Some(Self::BlueSize),
+            n if Self::AlphaSize as u32 == n => Some(Self::AlphaSize),
+            n if Self::DepthSize as u32 == n => Some(Self::DepthSize),
+            n if Self::StencilSize as u32 == n => Some(Self::StencilSize),
+            _ => None,
+        }
+    }
+}
+
+// GL_*_BUFFER targets of glBindBuffer
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum BufferTarget {
+    Array = 0x8892,
+    ElementArray = 0x8893,
+    PixelPack = 0x88eb,
+    PixelUnpack = 0x88ec,
+    Uniform = 0x8a11,
+    Texture = 0x8c2a,
+    TransformFeedback = 0x8c8e,
+    CopyRead = 0x8f36,
+    CopyWrite = 0x8f37,
+    DrawIndirect = 0x8f3f,
+    ShaderStorage = 0x90d2,
+    DispatchIndirect = 0x90ee,
+    Query = 0x9192,
+    AtomicCounter = 0x92c0,
+}
+
+impl BufferTarget {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Array as u32 == n => Some(Self::Array),
+            n if Self::ElementArray as u32 == n => Some(Self::ElementArray),
+            n if Self::PixelPack as u32 == n => Some(Self::PixelPack),
+            n if Self::PixelUnpack as u32 == n => Some(Self::PixelUnpack),
+            n if Self::Uniform as u32 == n => Some(Self::Uniform),
+            n if Self::Texture as u32 == n => Some(Self::Texture),
+            n if Self::TransformFeedback as u32 == n => Some(Self::TransformFeedback),
+            n if Self::CopyRead as u32 == n => Some(Self::CopyRead),
+            n if Self::CopyWrite as u32 == n => Some(Self::CopyWrite),
+            n if Self::DrawIndirect as u32 == n => Some(Self::DrawIndirect),
+            n if Self::ShaderStorage as u32 == n => Some(Self::ShaderStorage),
+            n if Self::DispatchIndirect as u32 == n => Some(Self::DispatchIndirect),
+            n if Self::Query as u32 == n => Some(Self::Query),
+            n if Self::AtomicCounter as u32 == n => Some(Self::AtomicCounter),
+            _ => None,
+        }
+    }
+
+    // Target whose binding is queried with `pname`
+    pub(crate) fn from_binding(pname: u32) -> Option<Self> {
+        match pname {
+            0x8894 => Some(Self::Array),
+            0x8895 => Some(Self::ElementArray),
+            0x88ed => Some(Self::PixelPack),
+            0x88ef => Some(Self::PixelUnpack),
+            0x8a28 => Some(Self::Uniform),
+            // GL_TEXTURE_BUFFER_BINDING shares its value with the target
+            0x8c2a => Some(Self::Texture),
+            0x8c8f => Some(Self::TransformFeedback),
+            // As do the copy targets
+            0x8f36 => Some(Self::CopyRead),
+            0x8f37 => Some(Self::CopyWrite),
+            0x8f43 => Some(Self::DrawIndirect),
+            0x90d3 => Some(Self::ShaderStorage),
+            0x90ef => Some(Self::DispatchIndirect),
+            0x9193 => Some(Self::Query),
+            0x92c1 => Some(Self::AtomicCounter),
+            _ => None,
+        }
+    }
+
+    // Number of indexed binding points, None for targets without them
+    pub(crate) fn binding_points(&self) -> Option<u32> {
+        match self {
+            Self::Uniform => Some(GL_MAX_UNIFORM_BUFFER_BINDINGS),
+            Self::TransformFeedback => Some(GL_MAX_TRANSFORM_FEEDBACK_BUFFERS),
+            Self::ShaderStorage => Some(GL_MAX_SHADER_STORAGE_BUFFER_BINDINGS),
+            Self::AtomicCounter => Some(GL_MAX_ATOMIC_COUNTER_BUFFER_BINDINGS),
+            _ => None,
+        }
+    }
+
+    // Alignment required of the offset of glBindBufferRange
+    pub(crate) fn offset_alignment(&self) -> usize {
+        match self {
+            Self::Uniform => GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT,
+            Self::ShaderStorage => GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT,
+            _ => 4,
+        }
+    }
+}
+
+// Start and size of an indexed binding queried with glGetIntegeri_v
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum IndexedBindingPName {
+    Binding(BufferTarget),
+    Start(BufferTarget),
+    Size(BufferTarget),
+}
+
+impl IndexedBindingPName {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x8a29 => Some(Self::Start(BufferTarget::Uniform)),
+            0x8a2a => Some(Self::Size(BufferTarget::Uniform)),
+            0x8c84 => Some(Self::Start(BufferTarget::TransformFeedback)),
+            0x8c85 => Some(Self::Size(BufferTarget::TransformFeedback)),
+            0x90d4 => Some(Self::Start(BufferTarget::ShaderStorage)),
+            0x90d5 => Some(Self::Size(BufferTarget::ShaderStorage)),
+            0x92c2 => Some(Self::Start(BufferTarget::AtomicCounter)),
+            0x92c3 => Some(Self::Size(BufferTarget::AtomicCounter)),
+            _ => BufferTarget::from_binding(value)
+                .filter(|target| target.binding_points().is_some())
+                .map(Self::Binding),
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BufferUsage {
+    StreamDraw = 0x88e0,
+    StreamRead = 0x88e1,
+    StreamCopy = 0x88e2,
+    StaticDraw = 0x88e4,
+    StaticRead = 0x88e5,
+    StaticCopy = 0x88e6,
+    DynamicDraw = 0x88e8,
+    DynamicRead = 0x88e9,
+    DynamicCopy = 0x88ea,
+}
+
+impl BufferUsage {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::StreamDraw as u32 == n => Some(Self::StreamDraw),
+            n if Self::StreamRead as u32 == n => Some(Self::StreamRead),
+            n if Self::StreamCopy as u32 == n => Some(Self::StreamCopy),
+            n if Self::StaticDraw as u32 == n => Some(Self::StaticDraw),
+            n if Self::StaticRead as u32 == n => Some(Self::StaticRead),
+            n if Self::StaticCopy as u32 == n => Some(Self::StaticCopy),
+            n if Self::DynamicDraw as u32 == n => Some(Self::DynamicDraw),
+            n if Self::DynamicRead as u32 == n => Some(Self::DynamicRead),
+            n if Self::DynamicCopy as u32 == n => Some(Self::DynamicCopy),
+            _ => None,
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BufferParameter {
+    ImmutableStorage = 0x821f,
+    StorageFlags = 0x8220,
+    Size = 0x8764,
+    Usage = 0x8765,
+    Access = 0x88bb,
+    Mapped = 0x88bc,
+    AccessFlags = 0x911f,
+    MapLength = 0x9120,
+    MapOffset = 0x9121,
+}
+
+impl BufferParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::ImmutableStorage as u32 == n => Some(Self::ImmutableStorage),
+            n if Self::StorageFlags as u32 == n => Some(Self::StorageFlags),
+            n if Self::Size as u32 == n => Some(Self::Size),
+            n if Self::Usage as u32 == n => Some(Self::Usage),
+            n if Self::Access as u32 == n => Some(Self::Access),
+            n if Self::Mapped as u32 == n => Some(Self::Mapped),
+            n if Self::AccessFlags as u32 == n => Some(Self::AccessFlags),
+            n if Self::MapLength as u32 == n => Some(Self::MapLength),
+            n if Self::MapOffset as u32 == n => Some(Self::MapOffset),
+            _ => None,
+        }
+    }
+}
+
+pub static GL_BUFFER_MAP_POINTER: u32 = 0x88bd;
+
+// Access of glMapBuffer, also reported as GL_BUFFER_ACCESS
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BufferAccess {
+    ReadOnly = 0x88b8,
+    WriteOnly = 0x88b9,
+    ReadWrite = 0x88ba,
+}
+
+impl BufferAccess {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::ReadOnly as u32 == n => Some(Self::ReadOnly),
+            n if Self::WriteOnly as u32 == n => Some(Self::WriteOnly),
+            n if Self::ReadWrite as u32 == n => Some(Self::ReadWrite),
+            _ => None,
+        }
+    }
+}
+
+// Access bits of glMapBufferRange. The read, write, persistent and coherent bits double as storage flags
+// of glBufferStorage.
+pub static GL_MAP_READ_BIT: u32 = 0x1;
+pub static GL_MAP_WRITE_BIT: u32 = 0x2;
+pub static GL_MAP_INVALIDATE_RANGE_BIT: u32 = 0x4;
+pub static GL_MAP_INVALIDATE_BUFFER_BIT: u32 = 0x8;
+pub static GL_MAP_FLUSH_EXPLICIT_BIT: u32 = 0x10;
+pub static GL_MAP_UNSYNCHRONIZED_BIT: u32 = 0x20;
+pub static GL_MAP_PERSISTENT_BIT: u32 = 0x40;
+pub static GL_MAP_COHERENT_BIT: u32 = 0x80;
+pub static GL_DYNAMIC_STORAGE_BIT: u32 = 0x100;
+pub static GL_CLIENT_STORAGE_BIT: u32 = 0x200;
+
+// Component types of vertex attributes
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum VertexAttribType {
+    Byte = 0x1400,
+    UnsignedByte = 0x1401,
+    Short = 0x1402,
+    UnsignedShort = 0x1403,
+    Int = 0x1404,
+    UnsignedInt = 0x1405,
+    Float = 0x1406,
+    Double = 0x140a,
+    HalfFloat = 0x140b,
+    Fixed = 0x140c,
+    UnsignedInt2101010Rev = 0x8368,
+    Int2101010Rev = 0x8d9f,
+}
+
+impl VertexAttribType {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Byte as u32 == n => Some(Self::Byte),
+            n if Self::UnsignedByte as u32 == n => Some(Self::UnsignedByte),
+            n if Self::Short as u32 == n => Some(Self::Short),
+            n if Self::UnsignedShort as u32 == n => Some(Self::UnsignedShort),
+            n if Self::Int as u32 == n => Some(Self::Int),
+            n if Self::UnsignedInt as u32 == n => Some(Self::UnsignedInt),
+            n if Self::Float as u32 == n => Some(Self::Float),
+            n if Self::Double as u32 == n => Some(Self::Double),
+            n if Self::HalfFloat as u32 == n => Some(Self::HalfFloat),
+            n if Self::Fixed as u32 == n => Some(Self::Fixed),
+            n if Self::UnsignedInt2101010Rev as u32 == n => Some(Self::UnsignedInt2101010Rev),
+            n if Self::Int2101010Rev as u32 == n => Some(Self::Int2101010Rev),
+            _ => None,
+        }
+    }
+
+    // Types whose four components are packed into a single word
+    pub(crate) fn is_packed(&self) -> bool {
+        matches!(self, Self::UnsignedInt2101010Rev | Self::Int2101010Rev)
+    }
+
+    // Types accepted by glVertexAttribIPointer
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Self::Byte | Self::UnsignedByte | Self::Short | Self::UnsignedShort | Self::Int | Self::UnsignedInt
+        )
+    }
+
+    pub(crate) fn is_signed(&self) -> bool {
+        matches!(self, Self::Byte | Self::Short | Self::Int | Self::Int2101010Rev)
+    }
+
+    // Bytes of a single component, or of the whole word of packed types
+    pub(crate) fn component_size(&self) -> usize {
+        match self {
+            Self::Byte | Self::UnsignedByte => 1,
+            Self::Short | Self::UnsignedShort | Self::HalfFloat => 2,
+            Self::Double => 8,
+            _ => 4,
+        }
+    }
+}
+
+// Size of vertex attributes whose components are stored in BGRA order
+pub static GL_BGRA: i32 = 0x80e1;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum VertexAttribParameter {
+    Enabled = 0x8622,
+    Size = 0x8623,
+    Stride = 0x8624,
+    Type = 0x8625,
+    Normalized = 0x886a,
+    BufferBinding = 0x889f,
+    Integer = 0x88fd,
+    Divisor = 0x88fe,
+    Binding = 0x82d4,
+    RelativeOffset = 0x82d5,
+}
+
+impl VertexAttribParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Enabled as u32 == n => Some(Self::Enabled),
+            n if Self::Size as u32 == n => Some(Self::Size),
+            n if Self::Stride as u32 == n => Some(Self::Stride),
+            n if Self::Type as u32 == n => Some(Self::Type),
+            n if Self::Normalized as u32 == n => Some(Self::Normalized),
+            n if Self::BufferBinding as u32 == n => Some(Self::BufferBinding),
+            n if Self::Integer as u32 == n => Some(Self::Integer),
+            n if Self::Divisor as u32 == n => Some(Self::Divisor),
+            n if Self::Binding as u32 == n => Some(Self::Binding),
+            n if Self::RelativeOffset as u32 == n => Some(Self::RelativeOffset),
+            _ => None,
+        }
+    }
+}
+
+pub static GL_VERTEX_ATTRIB_ARRAY_POINTER: u32 = 0x8645;
+
+// State of the vertex buffer bindings queried with glGetIntegeri_v
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum VertexBindingPName {
+    Divisor = 0x82d6,
+    Offset = 0x82d7,
+    Stride = 0x82d8,
+    Buffer = 0x8f4f,
+}
+
+impl VertexBindingPName {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Divisor as u32 == n => Some(Self::Divisor),
+            n if Self::Offset as u32 == n => Some(Self::Offset),
+            n if Self::Stride as u32 == n => Some(Self::Stride),
+            n if Self::Buffer as u32 == n => Some(Self::Buffer),
+            _ => None,
+        }
+    }
+}
+
+// Formats of pixel data passed to or from the application
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PixelFormat {
+    StencilIndex = 0x1901,
+    DepthComponent = 0x1902,
+    Red = 0x1903,
+    Green = 0x1904,
+    Blue = 0x1905,
+    Alpha = 0x1906,
+    Rgb = 0x1907,
+    Rgba = 0x1908,
+    Bgr = 0x80e0,
+    Bgra = 0x80e1,
+    Rg = 0x8227,
+    DepthStencil = 0x84f9,
+    RedInteger = 0x8d94,
+    GreenInteger = 0x8d95,
+    BlueInteger = 0x8d96,
+    RgInteger = 0x8228,
+    RgbInteger = 0x8d98,
+    RgbaInteger = 0x8d99,
+    BgrInteger = 0x8d9a,
+    BgraInteger = 0x8d9b,
+}
+
+impl PixelFormat {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::StencilIndex as u32 == n => Some(Self::StencilIndex),
+            n if Self::DepthComponent as u32 == n => Some(Self::DepthComponent),
+            n if Self::Red as u32 == n => Some(Self::Red),
+            n if Self::Green as u32 == n => Some(Self::Green),
+            n if Self::Blue as u32 == n => Some(Self::Blue),
+            n if Self::Alpha as u32 == n => Some(Self::Alpha),
+            n if Self::Rgb as u32 == n => Some(Self::Rgb),
+            n if Self::Rgba as u32 == n => Some(Self::Rgba),
+            n if Self::Bgr as u32 == n => Some(Self::Bgr),
+            n if Self::Bgra as u32 == n => Some(Self::Bgra),
+            n if Self::Rg as u32 == n => Some(Self::Rg),
+            n if Self::DepthStencil as u32 == n => Some(Self::DepthStencil),
+            n if Self::RedInteger as u32 == n => Some(Self::RedInteger),
+            n if Self::GreenInteger as u32 == n => Some(Self::GreenInteger),
+            n if Self::BlueInteger as u32 == n => Some(Self::BlueInteger),
+            n if Self::RgInteger as u32 == n => Some(Self::RgInteger),
+            n if Self::RgbInteger as u32 == n => Some(Self::RgbInteger),
+            n if Self::RgbaInteger as u32 == n => Some(Self::RgbaInteger),
+            n if Self::BgrInteger as u32 == n => Some(Self::BgrInteger),
+            n if Self::BgraInteger as u32 == n => Some(Self::BgraInteger),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn component_count(&self) -> usize {
+        match self {
+            Self::Rg | Self::RgInteger | Self::DepthStencil => 2,
+            Self::Rgb | Self::Bgr | Self::RgbInteger | Self::BgrInteger => 3,
+            Self::Rgba | Self::Bgra | Self::RgbaInteger | Self::BgraInteger => 4,
+            _ => 1,
+        }
+    }
+
+    // Formats transferring unconverted integer color components
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Self::RedInteger
+                | Self::GreenInteger
+                | Self::BlueInteger
+                | Self::RgInteger
+                | Self::RgbInteger
+                | Self::RgbaInteger
+                | Self::BgrInteger
+                | Self::BgraInteger
+        )
+    }
+}
+
+// Types of pixel data passed to or from the application
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PixelType {
+    Byte = 0x1400,
+    UnsignedByte = 0x1401,
+    Short = 0x1402,
+    UnsignedShort = 0x1403,
+    Int = 0x1404,
+    UnsignedInt = 0x1405,
+    Float = 0x1406,
+    HalfFloat = 0x140b,
+    UnsignedByte332 = 0x8032,
+    UnsignedShort4444 = 0x8033,
+    UnsignedShort5551 = 0x8034,
+    UnsignedInt8888 = 0x8035,
+    UnsignedInt1010102 = 0x8036,
+    UnsignedByte233Rev = 0x8362,
+    UnsignedShort565 = 0x8363,
+    UnsignedShort565Rev = 0x8364,
+    UnsignedShort4444Rev = 0x8365,
+    UnsignedShort1555Rev = 0x8366,
+    UnsignedInt8888Rev = 0x8367,
+    UnsignedInt2101010Rev = 0x8368,
+    UnsignedInt248 = 0x84fa,
+    UnsignedInt10F11F11FRev = 0x8c3b,
+    UnsignedInt5999Rev = 0x8c3e,
+    Float32UnsignedInt248Rev = 0x8dad,
+}
+
+impl PixelType {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Byte as u32 == n => Some(Self::Byte),
+            n if Self::UnsignedByte as u32 == n => Some(Self::UnsignedByte),
+            n if Self::Short as u32 == n => Some(Self::Short),
+            n if Self::UnsignedShort as u32 == n => Some(Self::UnsignedShort),
+            n if Self::Int as u32 == n => Some(Self::Int),
+            n if Self::UnsignedInt as u32 == n => Some(Self::UnsignedInt),
+            n if Self::Float as u32 == n => Some(Self::Float),
+            n if Self::HalfFloat as u32 == n => Some(Self::HalfFloat),
+            n if Self::UnsignedByte332 as u32 == n => Some(Self::UnsignedByte332),
+            n if Self::UnsignedShort4444 as u32 == n => Some(Self::UnsignedShort4444),
+            n if Self::UnsignedShort5551 as u32 == n => Some(Self::UnsignedShort5551),
+            n if Self::UnsignedInt8888 as u32 == n => Some(Self::UnsignedInt8888),
+            n if Self::UnsignedInt1010102 as u32 == n => Some(Self::UnsignedInt1010102),
+            n if Self::UnsignedByte233Rev as u32 == n => Some(Self::UnsignedByte233Rev),
+            n if Self::UnsignedShort565 as u32 == n => Some(Self::UnsignedShort565),
+            n if Self::UnsignedShort565Rev as u32 == n => Some(Self::UnsignedShort565Rev),
+            n if Self::UnsignedShort4444Rev as u32 == n => Some(Self::UnsignedShort4444Rev),
+            n if Self::UnsignedShort1555Rev as u32 == n => Some(Self::UnsignedShort1555Rev),
+            n if Self::UnsignedInt8888Rev as u32 == n => Some(Self::UnsignedInt8888Rev),
+            n if Self::UnsignedInt2101010Rev as u32 == n => Some(Self::UnsignedInt2101010Rev),
+            n if Self::UnsignedInt248 as u32 == n => Some(Self::UnsignedInt248),
+            n if Self::UnsignedInt10F11F11FRev as u32 == n => Some(Self::UnsignedInt10F11F11FRev),
+            n if Self::UnsignedInt5999Rev as u32 == n => Some(Self::UnsignedInt5999Rev),
+            n if Self::Float32UnsignedInt248Rev as u32 == n => Some(Self::Float32UnsignedInt248Rev),
+            _ => None,
+        }
+    }
+
+    // Size in bytes of one component, or of a whole pixel for packed types
+    pub(crate) fn size(&self) -> usize {
+        match self {
+            Self::Byte | Self::UnsignedByte | Self::UnsignedByte332 | Self::UnsignedByte233Rev => 1,
+            Self::Short
+            | Self::UnsignedShort
+            | Self::HalfFloat
+            | Self::UnsignedShort4444
+            | Self::UnsignedShort5551
+            | Self::UnsignedShort565
+            | Self::UnsignedShort565Rev
+            | Self::UnsignedShort4444Rev
+            | Self::UnsignedShort1555Rev => 2,
+            Self::Float32UnsignedInt248Rev => 8,
+            _ => 4,
+        }
+    }
+
+    // Number of components of a packed type, None for types storing one component per element
+    pub(crate) fn packed_components(&self) -> Option<usize> {
+        match self {
+            Self::Byte
+            | Self::UnsignedByte
+            | Self::Short
+            | Self::UnsignedShort
+            | Self::Int
+            | Self::UnsignedInt
+            | Self::Float
+            | Self::HalfFloat => None,
+            Self::UnsignedInt248 | Self::Float32UnsignedInt248Rev => Some(2),
+            Self::UnsignedByte332
+            | Self::UnsignedByte233Rev
+            | Self::UnsignedShort565
+            | Self::UnsignedShort565Rev
+            | Self::UnsignedInt10F11F11FRev
+            | Self::UnsignedInt5999Rev => Some(3),
+            _ => Some(4),
+        }
+    }
+}
+
+// Parameters of glPixelStore*
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PixelStoreParameter {
+    UnpackSwapBytes = 0x0cf0,
+    UnpackLsbFirst = 0x0cf1,
+    UnpackRowLength = 0x0cf2,
+    UnpackSkipRows = 0x0cf3,
+    UnpackSkipPixels = 0x0cf4,
+    UnpackAlignment = 0x0cf5,
+    PackSwapBytes = 0x0d00,
+    PackLsbFirst = 0x0d01,
+    PackRowLength = 0x0d02,
+    PackSkipRows = 0x0d03,
+    PackSkipPixels = 0x0d04,
+    PackAlignment = 0x0d05,
+    PackSkipImages = 0x806b,
+    PackImageHeight = 0x806c,
+    UnpackSkipImages = 0x806d,
+    UnpackImageHeight = 0x806e,
+}
+
+impl PixelStoreParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::UnpackSwapBytes as u32 == n => Some(Self::UnpackSwapBytes),
+            n if Self::UnpackLsbFirst as u32 == n => Some(Self::UnpackLsbFirst),
+            n if Self::UnpackRowLength as u32 == n => Some(Self::UnpackRowLength),
+            n if Self::UnpackSkipRows as u32 == n => Some(Self::UnpackSkipRows),
+            n if Self::UnpackSkipPixels as u32 == n => Some(Self::UnpackSkipPixels),
+            n if Self::UnpackAlignment as u32 == n => Some(Self::UnpackAlignment),
+            n if Self::PackSwapBytes as u32 == n => Some(Self::PackSwapBytes),
+            n if Self::PackLsbFirst as u32 == n => Some(Self::PackLsbFirst),
+            n if Self::PackRowLength as u32 == n => Some(Self::PackRowLength),
+            n if Self::PackSkipRows as u32 == n => Some(Self::PackSkipRows),
+            n if Self::PackSkipPixels as u32 == n => Some(Self::PackSkipPixels),
+            n if Self::PackAlignment as u32 == n => Some(Self::PackAlignment),
+            n if Self::PackSkipImages as u32 == n => Some(Self::PackSkipImages),
+            n if Self::PackImageHeight as u32 == n => Some(Self::PackImageHeight),
+            n if Self::UnpackSkipImages as u32 == n => Some(Self::UnpackSkipImages),
+            n if Self::UnpackImageHeight as u32 == n => Some(Self::UnpackImageHeight),
+            _ => None,
+        }
+    }
+}
+
+// Results of glCheckFramebufferStatus
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FramebufferStatus {
+    Complete = 0x8cd5,
+    IncompleteAttachment = 0x8cd6,
+    IncompleteMissingAttachment = 0x8cd7,
+    Unsupported = 0x8cdd,
+    IncompleteMultisample = 0x8d56,
+    IncompleteLayerTargets = 0x8da8,
+}
+
+// GL_FRAMEBUFFER_DEFAULT_* parameters of framebuffer objects set by glFramebufferParameteri
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FramebufferParameter {
+    Width = 0x9310,
+    Height = 0x9311,
+    Layers = 0x9312,
+    Samples = 0x9313,
+    FixedSampleLocations = 0x9314,
+}
+
+impl FramebufferParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Width as u32 == n => Some(Self::Width),
+            n if Self::Height as u32 == n => Some(Self::Height),
+            n if Self::Layers as u32 == n => Some(Self::Layers),
+            n if Self::Samples as u32 == n => Some(Self::Samples),
+            n if Self::FixedSampleLocations as u32 == n => Some(Self::FixedSampleLocations),
+            _ => None,
+        }
+    }
+}
+
+// Attachment points of a framebuffer object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AttachmentPoint {
+    Color(usize),
+    Depth,
+    Stencil,
+    // Attaches the same image to both the depth and the stencil attachment point
+    DepthStencil,
+}
+
+impl AttachmentPoint {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x8d00 => Some(Self::Depth),
+            0x8d20 => Some(Self::Stencil),
+            0x821a => Some(Self::DepthStencil),
+            n => match DrawBufferFBO::from_u32(n) {
+                Some(DrawBufferFBO::None) | None => None,
+                Some(attachment) => Some(Self::Color(attachment.get_attachment_index())),
+            },
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TextureTarget {
+    Texture1D = 0x0de0,
+    Texture2D = 0x0de1,
+    Texture3D = 0x806f,
+    Texture1DArray = 0x8c18,
+    Texture2DArray = 0x8c1a,
+    TextureRectangle = 0x84f5,
+    TextureCubeMap = 0x8513,
+    TextureCubeMapArray = 0x9009,
+    TextureBuffer = 0x8c2a,
+    Texture2DMultisample = 0x9100,
+    Texture2DMultisampleArray = 0x9102,
+}
+
+impl TextureTarget {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Texture1D as u32 == n => Some(Self::Texture1D),
+            n if Self::Texture2D as u32 == n => Some(Self::Texture2D),
+            n if Self::Texture3D as u32 == n => Some(Self::Texture3D),
+            n if Self::Texture1DArray as u32 == n => Some(Self::Texture1DArray),
+            n if Self::Texture2DArray as u32 == n => Some(Self::Texture2DArray),
+            n if Self::TextureRectangle as u32 == n => Some(Self::TextureRectangle),
+            n if Self::TextureCubeMap as u32 == n => Some(Self::TextureCubeMap),
+            n if Self::TextureCubeMapArray as u32 == n => Some(Self::TextureCubeMapArray),
+            n if Self::TextureBuffer as u32 == n => Some(Self::TextureBuffer),
+            n if Self::Texture2DMultisample as u32 == n => Some(Self::Texture2DMultisample),
+            n if Self::Texture2DMultisampleArray as u32 == n => Some(Self::Texture2DMultisampleArray),
+            _ => None,
+        }
+    }
+
+    // Targets whose images have more than one layer, cube map faces counting as layers
+    pub(crate) fn is_layered(&self) -> bool {
+        matches!(
+            self,
+            Self::Texture3D
+                | Self::Texture1DArray
+                | Self::Texture2DArray
+                | Self::TextureCubeMap
+                | Self::TextureCubeMapArray
+                | Self::Texture2DMultisampleArray
+        )
+    }
+
+    // Targets without mipmaps
+    pub(crate) fn has_single_level(&self) -> bool {
+        matches!(
+            self,
+            Self::TextureRectangle | Self::TextureBuffer | Self::Texture2DMultisample | Self::Texture2DMultisampleArray
+        )
+    }
+}
+
+// Cube map faces as accepted by glFramebufferTexture2D, in the order the faces are stored in
+pub static GL_TEXTURE_CUBE_MAP_POSITIVE_X: u32 = 0x8515;
+pub static GL_TEXTURE_CUBE_MAP_NEGATIVE_Z: u32 = 0x851a;
+
+// GL_*_SHADER types of glCreateShader
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ShaderType {
+    Fragment = 0x8b30,
+    Vertex = 0x8b31,
+    Geometry = 0x8dd9,
+    TessEvaluation = 0x8e87,
+    TessControl = 0x8e88,
+    Compute = 0x91b9,
+}
+
+impl ShaderType {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Fragment as u32 == n => Some(Self::Fragment),
+            n if Self::Vertex as u32 == n => Some(Self::Vertex),
+            n if Self::Geometry as u32 == n => Some(Self::Geometry),
+            n if Self::TessEvaluation as u32 == n => Some(Self::TessEvaluation),
+            n if Self::TessControl as u32 == n => Some(Self::TessControl),
+            n if Self::Compute as u32 == n => Some(Self::Compute),
+            _ => None,
+        }
+    }
+}
+
+// Parameters of glGetShaderiv
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ShaderParameter {
+    ShaderType = 0x8b4f,
+    DeleteStatus = 0x8b80,
+    CompileStatus = 0x8b81,
+    InfoLogLength = 0x8b84,
+    ShaderSourceLength = 0x8b88,
+}
+
+impl ShaderParameter {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::ShaderType as u32 == n => Some(Self::ShaderType),
+            n if Self::DeleteStatus as u32 == n => Some(Self::DeleteStatus),
+            n if Self::CompileStatus as u32 == n => Some(Self::CompileStatus),
+            n if Self::InfoLogLength as u32 == n => Some(Self::InfoLogLength),
+            n if Self::ShaderSourceLength as u32 == n => Some(Self::ShaderSourceLength),
+            _ => None,
+        }
+    }
+}
+
+// Buffers of glClearBuffer*
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ClearBuffer {
+    Color = 0x1800,
+    Depth = 0x1801,
+    Stencil = 0x1802,
+    DepthStencil = 0x84f9,
+}
+
+impl ClearBuffer {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Color as u32 == n => Some(Self::Color),
+            n if Self::Depth as u32 == n => Some(Self::Depth),
+            n if Self::Stencil as u32 == n => Some(Self::Stencil),
+            n if Self::DepthStencil as u32 == n => Some(Self::DepthStencil),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum Framebuffer {
     Default,          // Known as the Default Framebuffer
     UserDefined(u32), //FBO
@@ -130,3 +1323,216 @@ impl DrawBufferFBO {
         }
     }
 }
+
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Face {
+    Front = 0x404,
+    Back = 0x405,
+    FrontAndBack = 0x408,
+}
+
+impl Face {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::Front as u32 == n => Some(Self::Front),
+            n if Self::Back as u32 == n => Some(Self::Back),
+            n if Self::FrontAndBack as u32 == n => Some(Self::FrontAndBack),
+            _ => None,
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Capability {
+    LineSmooth = 0x0b20,
+    PolygonSmooth = 0x0b41,
+    CullFace = 0x0b44,
+    DepthTest = 0x0b71,
+    StencilTest = 0x0b90,
+    Dither = 0x0bd0,
+    Blend = 0x0be2,
+    ColorLogicOp = 0x0bf2,
+    ScissorTest = 0x0c11,
+    PolygonOffsetPoint = 0x2a01,
+    PolygonOffsetLine = 0x2a02,
+    PolygonOffsetFill = 0x8037,
+    Multisample = 0x809d,
+    SampleAlphaToCoverage = 0x809e,
+    SampleAlphaToOne = 0x809f,
+    SampleCoverage = 0x80a0,
+    ProgramPointSize = 0x8642,
+    DepthClamp = 0x864f,
+    TextureCubeMapSeamless = 0x884f,
+    SampleShading = 0x8c36,
+    RasterizerDiscard = 0x8c89,
+    PrimitiveRestartFixedIndex = 0x8d69,
+    FramebufferSrgb = 0x8db9,
+    SampleMask = 0x8e51,
+    PrimitiveRestart = 0x8f9d,
+}
+
+impl Capability {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::LineSmooth as u32 == n => Some(Self::LineSmooth),
+            n if Self::PolygonSmooth as u32 == n => Some(Self::PolygonSmooth),
+            n if Self::CullFace as u32 == n => Some(Self::CullFace),
+            n if Self::DepthTest as u32 == n => Some(Self::DepthTest),
+            n if Self::StencilTest as u32 == n => Some(Self::StencilTest),
+            n if Self::Dither as u32 == n => Some(Self::Dither),
+            n if Self::Blend as u32 == n => Some(Self::Blend),
+            n if Self::ColorLogicOp as u32 == n => Some(Self::ColorLogicOp),
+            n if Self::ScissorTest as u32 == n => Some(Self::ScissorTest),
+            n if Self::PolygonOffsetPoint as u32 == n => Some(Self::PolygonOffsetPoint),
+            n if Self::PolygonOffsetLine as u32 == n => Some(Self::PolygonOffsetLine),
+            n if Self::PolygonOffsetFill as u32 == n => Some(Self::PolygonOffsetFill),
+            n if Self::Multisample as u32 == n => Some(Self::Multisample),
+            n if Self::SampleAlphaToCoverage as u32 == n => Some(Self::SampleAlphaToCoverage),
+            n if Self::SampleAlphaToOne as u32 == n => Some(Self::SampleAlphaToOne),
+            n if Self::SampleCoverage as u32 == n => Some(Self::SampleCoverage),
+            n if Self::ProgramPointSize as u32 == n => Some(Self::ProgramPointSize),
+            n if Self::DepthClamp as u32 == n => Some(Self::DepthClamp),
+            n if Self::TextureCubeMapSeamless as u32 == n => Some(Self::TextureCubeMapSeamless),
+            n if Self::SampleShading as u32 == n => Some(Self::SampleShading),
+            n if Self::RasterizerDiscard as u32 == n => Some(Self::RasterizerDiscard),
+            n if Self::PrimitiveRestartFixedIndex as u32 == n => Some(Self::PrimitiveRestartFixedIndex),
+            n if Self::FramebufferSrgb as u32 == n => Some(Self::FramebufferSrgb),
+            n if Self::SampleMask as u32 == n => Some(Self::SampleMask),
+            n if Self::PrimitiveRestart as u32 == n => Some(Self::PrimitiveRestart),
+            _ => None,
+        }
+    }
+
+    // Number of separately enabled indices for glEnablei. Zero if the capability is not indexed.
+    pub(crate) fn indices(&self) -> usize {
+        match self {
+            Self::Blend => GL_MAX_DRAW_BUFFERS,
+            // One per viewport, and there is only a single viewport
+            Self::ScissorTest => 1,
+            _ => 0,
+        }
+    }
+}
+
+// State that can be queried through glGet*. DRAW_BUFFERi and capabilities are handled separately.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GetPName {
+    DepthWritemask = 0x0b72,
+    DepthClearValue = 0x0b73,
+    StencilClearValue = 0x0b91,
+    StencilWritemask = 0x0b98,
+    Viewport = 0x0ba2,
+    DrawBuffer = 0x0c01,
+    ScissorBox = 0x0c10,
+    ColorClearValue = 0x0c22,
+    ColorWritemask = 0x0c23,
+    Doublebuffer = 0x0c32,
+    MaxViewportDims = 0x0d3a,
+    SubpixelBits = 0x0d50,
+    RenderbufferBinding = 0x8ca7,
+    ReadBuffer = 0x0c02,
+    MaxRenderbufferSize = 0x84e8,
+    TextureBinding1D = 0x8068,
+    TextureBinding2D = 0x8069,
+    TextureBinding3D = 0x806a,
+    SampleBuffers = 0x80a8,
+    Samples = 0x80a9,
+    TextureBindingRectangle = 0x84f6,
+    TextureBindingCubeMap = 0x8514,
+    MaxDrawBuffers = 0x8824,
+    TextureBinding1DArray = 0x8c1c,
+    TextureBinding2DArray = 0x8c1d,
+    StencilBackWritemask = 0x8ca5,
+    TextureBindingBuffer = 0x8c2c,
+    DrawFramebufferBinding = 0x8ca6,
+    ReadFramebufferBinding = 0x8caa,
+    MaxColorAttachments = 0x8cdf,
+    MaxSamples = 0x8d57,
+    VertexArrayBinding = 0x85b5,
+    MaxVertexAttribs = 0x8869,
+    MaxVertexAttribStride = 0x82e5,
+    MaxVertexAttribBindings = 0x82da,
+    MaxVertexAttribRelativeOffset = 0x82d9,
+    MaxUniformBufferBindings = 0x8a2f,
+    UniformBufferOffsetAlignment = 0x8a34,
+    MaxTransformFeedbackBuffers = 0x8e70,
+    MaxShaderStorageBufferBindings = 0x90dd,
+    ShaderStorageBufferOffsetAlignment = 0x90df,
+    MaxAtomicCounterBufferBindings = 0x92dc,
+    TextureBindingCubeMapArray = 0x900a,
+    TextureBinding2DMultisample = 0x9104,
+    TextureBinding2DMultisampleArray = 0x9105,
+    MaxColorTextureSamples = 0x910e,
+    MaxDepthTextureSamples = 0x910f,
+    MaxIntegerSamples = 0x9110,
+    MaxFramebufferWidth = 0x9315,
+    MaxFramebufferHeight = 0x9316,
+    MaxFramebufferLayers = 0x9317,
+    MaxFramebufferSamples = 0x9318,
+}
+
+pub static GL_DRAW_BUFFER0: u32 = 0x8825;
+
+impl GetPName {
+    pub(crate) fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            n if Self::DepthWritemask as u32 == n => Some(Self::DepthWritemask),
+            n if Self::DepthClearValue as u32 == n => Some(Self::DepthClearValue),
+            n if Self::StencilClearValue as u32 == n => Some(Self::StencilClearValue),
+            n if Self::StencilWritemask as u32 == n => Some(Self::StencilWritemask),
+            n if Self::Viewport as u32 == n => Some(Self::Viewport),
+            n if Self::DrawBuffer as u32 == n => Some(Self::DrawBuffer),
+            n if Self::ScissorBox as u32 == n => Some(Self::ScissorBox),
+            n if Self::ColorClearValue as u32 == n => Some(Self::ColorClearValue),
+            n if Self::ColorWritemask as u32 == n => Some(Self::ColorWritemask),
+            n if Self::Doublebuffer as u32 == n => Some(Self::Doublebuffer),
+            n if Self::MaxViewportDims as u32 == n => Some(Self::MaxViewportDims),
+            n if Self::SubpixelBits as u32 == n => Some(Self::SubpixelBits),
+            n if Self::RenderbufferBinding as u32 == n => Some(Self::RenderbufferBinding),
+            n if Self::ReadBuffer as u32 == n => Some(Self::ReadBuffer),
+            n if Self::MaxRenderbufferSize as u32 == n => Some(Self::MaxRenderbufferSize),
+            n if Self::TextureBinding1D as u32 == n => Some(Self::TextureBinding1D),
+            n if Self::TextureBinding2D as u32 == n => Some(Self::TextureBinding2D),
+            n if Self::TextureBinding3D as u32 == n => Some(Self::TextureBinding3D),
+            n if Self::SampleBuffers as u32 == n => Some(Self::SampleBuffers),
+            n if Self::Samples as u32 == n => Some(Self::Samples),
+            n if Self::TextureBindingRectangle as u32 == n => Some(Self::TextureBindingRectangle),
+            n if Self::TextureBindingCubeMap as u32 == n => Some(Self::TextureBindingCubeMap),
+            n if Self::MaxDrawBuffers as u32 == n => Some(Self::MaxDrawBuffers),
+            n if Self::TextureBinding1DArray as u32 == n => Some(Self::TextureBinding1DArray),
+            n if Self::TextureBinding2DArray as u32 == n => Some(Self::TextureBinding2DArray),
+            n if Self::StencilBackWritemask as u32 == n => Some(Self::StencilBackWritemask),
+            n if Self::TextureBindingBuffer as u32 == n => Some(Self::TextureBindingBuffer),
+            n if Self::DrawFramebufferBinding as u32 == n => Some(Self::DrawFramebufferBinding),
+            n if Self::ReadFramebufferBinding as u32 == n => Some(Self::ReadFramebufferBinding),
+            n if Self::MaxColorAttachments as u32 == n => Some(Self::MaxColorAttachments),
+            n if Self::MaxSamples as u32 == n => Some(Self::MaxSamples),
+            n if Self::VertexArrayBinding as u32 == n => Some(Self::VertexArrayBinding),
+            n if Self::MaxVertexAttribs as u32 == n => Some(Self::MaxVertexAttribs),
+            n if Self::MaxVertexAttribStride as u32 == n => Some(Self::MaxVertexAttribStride),
+            n if Self::MaxVertexAttribBindings as u32 == n => Some(Self::MaxVertexAttribBindings),
+            n if Self::MaxVertexAttribRelativeOffset as u32 == n => Some(Self::MaxVertexAttribRelativeOffset),
+            n if Self::MaxUniformBufferBindings as u32 == n => Some(Self::MaxUniformBufferBindings),
+            n if Self::UniformBufferOffsetAlignment as u32 == n => Some(Self::UniformBufferOffsetAlignment),
+            n if Self::MaxTransformFeedbackBuffers as u32 == n => Some(Self::MaxTransformFeedbackBuffers),
+            n if Self::MaxShaderStorageBufferBindings as u32 == n => Some(Self::MaxShaderStorageBufferBindings),
+            n if Self::ShaderStorageBufferOffsetAlignment as u32 == n => Some(Self::ShaderStorageBufferOffsetAlignment),
+            n if Self::MaxAtomicCounterBufferBindings as u32 == n => Some(Self::MaxAtomicCounterBufferBindings),
+            n if Self::TextureBindingCubeMapArray as u32 == n => Some(Self::TextureBindingCubeMapArray),
+            n if Self::TextureBinding2DMultisample as u32 == n => Some(Self::TextureBinding2DMultisample),
+            n if Self::MaxFramebufferWidth as u32 == n => Some(Self::MaxFramebufferWidth),
+            n if Self::MaxFramebufferHeight as u32 == n => Some(Self::MaxFramebufferHeight),
+            n if Self::MaxFramebufferLayers as u32 == n => Some(Self::MaxFramebufferLayers),
+            n if Self::MaxFramebufferSamples as u32 == n => Some(Self::MaxFramebufferSamples),
+            n if Self::TextureBinding2DMultisampleArray as u32 == n => Some(Self::TextureBinding2DMultisampleArray),
+            n if Self::MaxColorTextureSamples as u32 == n => Some(Self::MaxColorTextureSamples),
+            n if Self::MaxDepthTextureSamples as u32 == n => Some(Self::MaxDepthTextureSamples),
+            n if Self::MaxIntegerSamples as u32 == n => Some(Self::MaxIntegerSamples),
+            _ => None,
+        }
+    }
+}
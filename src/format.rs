@@ -0,0 +1,384 @@
+use crate::{
+    enums::{ComponentType, InternalFormat},
+    types::ColorData,
+};
+
+// Color texels are stored in the layout of their internal format. Components of a format whose components all
+// have the same byte sized width are stored one after another, red first, each in native byte order. The remaining
+// formats pack their components into a single native order word, red in the least significant bits.
+
+// Writes the lowest `size` bytes of `bits` in native byte order
+pub(crate) fn write_bits(out: &mut [u8], bits: u64, size: usize) {
+    match size {
+        1 => out[0] = bits as u8,
+        2 => out[..2].copy_from_slice(&(bits as u16).to_ne_bytes()),
+        _ => out[..4].copy_from_slice(&(bits as u32).to_ne_bytes()),
+    }
+}
+
+fn read_bits(texel: &[u8], size: usize) -> u64 {
+    match size {
+        1 => texel[0] as u64,
+        2 => u16::from_ne_bytes([texel[0], texel[1]]) as u64,
+        _ => u32::from_ne_bytes([texel[0], texel[1], texel[2], texel[3]]) as u64,
+    }
+}
+
+fn mask(bits: u32) -> u64 {
+    (1u64 << bits) - 1
+}
+
+pub(crate) fn unorm(value: f64, bits: u32) -> u64 {
+    (value.clamp(0.0, 1.0) * mask(bits) as f64).round() as u64
+}
+
+// Unsigned float with a five bit exponent and `mantissa_bits` bits of mantissa, as used by half floats
+// and the packed 11 and 10 bit float formats
+pub(crate) fn unsigned_small_float(value: f32, mantissa_bits: u32) -> u64 {
+    if value.is_nan() {
+        return (0x1f << mantissa_bits) | 1;
+    }
+    let value = value.max(0.0);
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    if exponent >= 31 {
+        // Too large values and infinity become infinity
+        return 0x1f << mantissa_bits;
+    }
+    if exponent <= 0 {
+        // Denormals, rounding up into the smallest normal value when needed
+        return (value as f64 * 2f64.powi(14 + mantissa_bits as i32)).round() as u64;
+    }
+    ((exponent as u64) << mantissa_bits) | ((bits & 0x7fffff) >> (23 - mantissa_bits)) as u64
+}
+
+fn from_unsigned_small_float(bits: u64, mantissa_bits: u32) -> f32 {
+    let exponent = (bits >> mantissa_bits) as i32 & 0x1f;
+    let mantissa = (bits & mask(mantissa_bits)) as f64;
+    let value = match exponent {
+        0 => mantissa * 2f64.powi(-14 - mantissa_bits as i32),
+        0x1f if mantissa == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (1.0 + mantissa / (1u64 << mantissa_bits) as f64) * 2f64.powi(exponent - 15),
+    };
+    value as f32
+}
+
+pub(crate) fn half_float(value: f32) -> u64 {
+    let sign = if value.is_sign_negative() && !value.is_nan() { 0x8000 } else { 0 };
+    sign | unsigned_small_float(value.abs(), 10)
+}
+
+pub(crate) fn from_half_float(bits: u64) -> f32 {
+    let value = from_unsigned_small_float(bits & 0x7fff, 10);
+    if bits & 0x8000 != 0 { -value } else { value }
+}
+
+// Transfer functions of the sRGB formats. Only red, green and blue are converted, alpha is always linear.
+pub(crate) fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 { value / 12.92 } else { ((value + 0.055) / 1.055).powf(2.4) }
+}
+
+pub(crate) fn linear_to_srgb(value: f32) -> f32 {
+    match value {
+        _ if value.is_nan() || value <= 0.0 => 0.0,
+        _ if value < 0.0031308 => value * 12.92,
+        _ if value < 1.0 => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+        _ => 1.0,
+    }
+}
+
+// Bits of a single component of `width` bits
+fn encode_component(component_type: ComponentType, width: u32, value: f64) -> u64 {
+    match (component_type, width) {
+        (ComponentType::UnsignedNormalized, _) => unorm(value, width),
+        (ComponentType::Float, 32) => (value as f32).to_bits() as u64,
+        (ComponentType::Float, 16) => half_float(value as f32),
+        (ComponentType::Float, _) => unsigned_small_float(value as f32, width - 5),
+        (ComponentType::Int, _) => {
+            let max = mask(width - 1) as f64;
+            (value.clamp(-max - 1.0, max) as i64) as u64 & mask(width)
+        }
+        (ComponentType::UnsignedInt, _) => value.clamp(0.0, mask(width) as f64) as u64,
+    }
+}
+
+fn decode_component(component_type: ComponentType, width: u32, bits: u64) -> f64 {
+    match (component_type, width) {
+        (ComponentType::UnsignedNormalized, _) => bits as f64 / mask(width) as f64,
+        (ComponentType::Float, 32) => f32::from_bits(bits as u32) as f64,
+        (ComponentType::Float, 16) => from_half_float(bits) as f64,
+        (ComponentType::Float, _) => from_unsigned_small_float(bits, width - 5) as f64,
+        // Sign extended from the top bit of the component
+        (ComponentType::Int, _) => ((bits << (64 - width)) as i64 >> (64 - width)) as f64,
+        (ComponentType::UnsignedInt, _) => bits as f64,
+    }
+}
+
+// Widths of the red, green, blue and alpha components, and whether they are packed into a single word
+fn component_layout(format: InternalFormat) -> ([u32; 4], bool) {
+    let [red, green, blue, alpha, ..] = format.component_sizes();
+    let widths = [red, green, blue, alpha];
+    let packed = widths.iter().any(|&width| width != 0 && (width != red || width % 8 != 0));
+    (widths, packed)
+}
+
+// Converts `color` to `format` and writes it to `out`, which holds a single texel.
+// Values out of range of the format are clamped, components the format doesn't have are dropped.
+pub(crate) fn encode_color(format: InternalFormat, color: ColorData, out: &mut [u8]) {
+    let component_type = format.component_type();
+    let values = match component_type {
+        ComponentType::UnsignedNormalized | ComponentType::Float => color.to_floats().map(f64::from),
+        ComponentType::Int => color.to_ints().map(f64::from),
+        ComponentType::UnsignedInt => color.to_unsigned_ints().map(f64::from),
+    };
+    let (widths, packed) = component_layout(format);
+    let mut word = 0;
+    let mut offset = 0;
+    for (&width, &value) in widths.iter().zip(&values).filter(|(width, _)| **width != 0) {
+        let bits = encode_component(component_type, width, value);
+        if packed {
+            word |= bits << offset;
+        } else {
+            write_bits(&mut out[offset as usize / 8..], bits, width as usize / 8);
+        }
+        offset += width;
+    }
+    if packed {
+        write_bits(out, word, format.texel_size());
+    }
+}
+
+// Reads the texel in `texel`. Components the format doesn't have read back as 0, or 1 for alpha.
+pub(crate) fn decode_color(format: InternalFormat, texel: &[u8]) -> ColorData {
+    let component_type = format.component_type();
+    let (widths, packed) = component_layout(format);
+    let word = if packed { read_bits(texel, format.texel_size()) } else { 0 };
+    let mut values = [0.0, 0.0, 0.0, 1.0];
+    let mut offset = 0;
+    for (&width, value) in widths.iter().zip(&mut values).filter(|(width, _)| **width != 0) {
+        let bits = if packed {
+            (word >> offset) & mask(width)
+        } else {
+            read_bits(&texel[offset as usize / 8..], width as usize / 8)
+        };
+        *value = decode_component(component_type, width, bits);
+        offset += width;
+    }
+    match component_type {
+        ComponentType::UnsignedNormalized | ComponentType::Float => ColorData::Float(values.map(|value| value as f32)),
+        ComponentType::Int => ColorData::Int(values.map(|value| value as i32)),
+        ComponentType::UnsignedInt => ColorData::UnsignedInt(values.map(|value| value as u32)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::array;
+
+    use super::*;
+
+    use InternalFormat::*;
+
+    const COLOR_FORMATS: [InternalFormat; 39] = [
+        R8, Rg8, Rgb8, Rgba8, Srgb8, Srgb8Alpha8, Rgb10A2, Rgb565, R16, Rg16, Rgba16, R16F, Rg16F, Rgb16F, Rgba16F, R32F,
+        Rg32F, Rgb32F, Rgba32F, R11FG11FB10F, R8I, R8UI, R16I, R16UI, R32I, R32UI, Rg8I, Rg8UI, Rg16I, Rg16UI, Rg32I,
+        Rg32UI, Rgba8I, Rgba8UI, Rgba16I, Rgba16UI, Rgba32I, Rgba32UI, Rgb10A2UI,
+    ];
+
+    fn round_trip(format: InternalFormat, color: ColorData) -> ColorData {
+        let mut texel = vec![0u8; format.texel_size()];
+        encode_color(format, color, &mut texel);
+        decode_color(format, &texel)
+    }
+
+    fn encode(format: InternalFormat, color: ColorData) -> Vec<u8> {
+        let mut texel = vec![0u8; format.texel_size()];
+        encode_color(format, color, &mut texel);
+        texel
+    }
+
+    fn floats(color: ColorData) -> [f32; 4] {
+        match color {
+            ColorData::Float(color) => color,
+            _ => panic!("expected a float color, got {color:?}"),
+        }
+    }
+
+    fn ints(color: ColorData) -> [i32; 4] {
+        match color {
+            ColorData::Int(color) => color,
+            _ => panic!("expected an int color, got {color:?}"),
+        }
+    }
+
+    fn unsigned_ints(color: ColorData) -> [u32; 4] {
+        match color {
+            ColorData::UnsignedInt(color) => color,
+            _ => panic!("expected an unsigned int color, got {color:?}"),
+        }
+    }
+
+    // Native order word of a packed texel
+    fn word(texel: &[u8]) -> u64 {
+        read_bits(texel, texel.len())
+    }
+
+    #[test]
+    fn round_trips_every_color_format() {
+        for format in COLOR_FORMATS {
+            // Missing components read back as 0, or 1 for alpha
+            let sizes = format.component_sizes();
+            let expected = |values: [f32; 4]| array::from_fn(|index| if sizes[index] != 0 { values[index] } else { [0.0, 0.0, 0.0, 1.0][index] });
+            match format.component_type() {
+                ComponentType::UnsignedNormalized => {
+                    let color = [0.0, 1.0, 0.0, 1.0];
+                    assert_eq!(floats(round_trip(format, ColorData::Float(color))), expected(color), "{format:?}");
+                }
+                ComponentType::Float => {
+                    let color = [0.5, 2.0, 0.25, 1.0];
+                    assert_eq!(floats(round_trip(format, ColorData::Float(color))), expected(color), "{format:?}");
+                }
+                ComponentType::Int => {
+                    let color = ints(round_trip(format, ColorData::Int([-3, 7, -1, 1])));
+                    assert_eq!(color.map(|value| value as f32), expected([-3.0, 7.0, -1.0, 1.0]), "{format:?}");
+                }
+                ComponentType::UnsignedInt => {
+                    let color = unsigned_ints(round_trip(format, ColorData::UnsignedInt([3, 7, 0, 1])));
+                    assert_eq!(color.map(|value| value as f32), expected([3.0, 7.0, 0.0, 1.0]), "{format:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clamps_normalized_components() {
+        assert_eq!(encode(Rgba8, ColorData::Float([-1.0, 2.0, 0.5, 1.0])), [0, 255, 128, 255]);
+        assert_eq!(encode(Rgba8, ColorData::Float([f32::NEG_INFINITY, f32::INFINITY, 0.0, 0.0])), [0, 255, 0, 0]);
+        assert_eq!(encode(R16, ColorData::Float([1.5, 0.0, 0.0, 0.0])), 0xffffu16.to_ne_bytes());
+    }
+
+    #[test]
+    fn clamps_integer_components() {
+        assert_eq!(ints(round_trip(R8I, ColorData::Int([200, 0, 0, 0])))[0], 127);
+        assert_eq!(ints(round_trip(R8I, ColorData::Int([-200, 0, 0, 0])))[0], -128);
+        assert_eq!(ints(round_trip(R16I, ColorData::Int([i32::MIN, 0, 0, 0])))[0], i16::MIN as i32);
+        assert_eq!(unsigned_ints(round_trip(R8UI, ColorData::UnsignedInt([300, 0, 0, 0])))[0], 255);
+        assert_eq!(unsigned_ints(round_trip(R16UI, ColorData::Int([-5, 0, 0, 0])))[0], 0);
+        assert_eq!(unsigned_ints(round_trip(Rgb10A2UI, ColorData::UnsignedInt([2000, 0, 0, 9])))[0..4], [1023, 0, 0, 3]);
+    }
+
+    #[test]
+    fn sign_extends_signed_integer_components() {
+        assert_eq!(ints(decode_color(R8I, &[0xff])), [-1, 0, 0, 1]);
+        assert_eq!(ints(decode_color(R8I, &[0x80])), [-128, 0, 0, 1]);
+        let mut texel = Vec::new();
+        texel.extend_from_slice(&0x8000u16.to_ne_bytes());
+        texel.extend_from_slice(&0x7fffu16.to_ne_bytes());
+        assert_eq!(ints(decode_color(Rg16I, &texel)), [-32768, 32767, 0, 1]);
+        assert_eq!(ints(decode_color(R32I, &0x8000_0000u32.to_ne_bytes())), [i32::MIN, 0, 0, 1]);
+        assert_eq!(encode(R8I, ColorData::Int([-1, 0, 0, 0])), [0xff]);
+    }
+
+    #[test]
+    fn packs_rgb10_a2_red_first() {
+        assert_eq!(word(&encode(Rgb10A2, ColorData::Float([1.0, 0.0, 0.0, 0.0]))), 0x3ff);
+        assert_eq!(word(&encode(Rgb10A2, ColorData::Float([0.0, 1.0, 0.0, 0.0]))), 0x3ff << 10);
+        assert_eq!(word(&encode(Rgb10A2, ColorData::Float([0.0, 0.0, 1.0, 0.0]))), 0x3ff << 20);
+        assert_eq!(word(&encode(Rgb10A2, ColorData::Float([0.0, 0.0, 0.0, 1.0]))), 0x3 << 30);
+        let color = floats(decode_color(Rgb10A2, &(0x3ffu32 << 20 | 1 << 30).to_ne_bytes()));
+        assert_eq!(color, [0.0, 0.0, 1.0, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn packs_rgb565_red_first() {
+        assert_eq!(encode(Rgb565, ColorData::Float([1.0, 0.0, 0.0, 0.0])), 0x001fu16.to_ne_bytes());
+        assert_eq!(encode(Rgb565, ColorData::Float([0.0, 1.0, 0.0, 0.0])), 0x07e0u16.to_ne_bytes());
+        assert_eq!(encode(Rgb565, ColorData::Float([0.0, 0.0, 1.0, 0.0])), 0xf800u16.to_ne_bytes());
+        assert_eq!(floats(decode_color(Rgb565, &0xf81fu16.to_ne_bytes())), [1.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn converts_half_floats() {
+        assert_eq!(half_float(1.0), 0x3c00);
+        assert_eq!(half_float(-2.0), 0xc000);
+        assert_eq!(half_float(65504.0), 0x7bff);
+        assert_eq!(half_float(0.0), 0x0000);
+        assert_eq!(half_float(-0.0), 0x8000);
+        assert_eq!(from_half_float(0x3c00), 1.0);
+        assert_eq!(from_half_float(0xc000), -2.0);
+        assert_eq!(from_half_float(0x7bff), 65504.0);
+    }
+
+    #[test]
+    fn converts_half_float_denormals() {
+        // Smallest denormal and largest denormal
+        assert_eq!(half_float(2f32.powi(-24)), 0x0001);
+        assert_eq!(half_float(2f32.powi(-14) - 2f32.powi(-24)), 0x03ff);
+        assert_eq!(half_float(-2f32.powi(-15)), 0x8200);
+        assert_eq!(from_half_float(0x0001), 2f32.powi(-24));
+        assert_eq!(from_half_float(0x03ff), 2f32.powi(-14) - 2f32.powi(-24));
+        // Too small values flush to zero, and the smallest normal value stays normal
+        assert_eq!(half_float(2f32.powi(-26)), 0x0000);
+        assert_eq!(half_float(2f32.powi(-14)), 0x0400);
+    }
+
+    #[test]
+    fn converts_half_float_infinity_and_nan() {
+        assert_eq!(half_float(f32::INFINITY), 0x7c00);
+        assert_eq!(half_float(f32::NEG_INFINITY), 0xfc00);
+        // Values too large for a half float become infinity
+        assert_eq!(half_float(1e6), 0x7c00);
+        assert_eq!(half_float(-1e6), 0xfc00);
+        assert_eq!(from_half_float(0x7c00), f32::INFINITY);
+        assert_eq!(from_half_float(0xfc00), f32::NEG_INFINITY);
+        let nan = half_float(f32::NAN);
+        assert_eq!(nan & 0x7c00, 0x7c00);
+        assert_ne!(nan & 0x3ff, 0);
+        assert!(from_half_float(nan).is_nan());
+    }
+
+    #[test]
+    fn converts_small_unsigned_floats() {
+        // 11 bit floats have six bits of mantissa, 10 bit floats five
+        assert_eq!(unsigned_small_float(1.0, 6), 15 << 6);
+        assert_eq!(unsigned_small_float(1.0, 5), 15 << 5);
+        assert_eq!(unsigned_small_float(1.5, 6), 15 << 6 | 0x20);
+        assert_eq!(unsigned_small_float(65024.0, 6), 0x7bf);
+        assert_eq!(unsigned_small_float(64512.0, 5), 0x3df);
+        // Negative values clamp to zero
+        assert_eq!(unsigned_small_float(-1.0, 6), 0);
+        assert_eq!(unsigned_small_float(f32::NEG_INFINITY, 5), 0);
+        assert_eq!(unsigned_small_float(f32::INFINITY, 6), 0x7c0);
+        assert_eq!(unsigned_small_float(1e6, 5), 0x3e0);
+        assert_eq!(unsigned_small_float(2f32.powi(-20), 6), 1);
+        assert_eq!(unsigned_small_float(2f32.powi(-19), 5), 1);
+        assert_eq!(from_unsigned_small_float(1, 6), 2f32.powi(-20));
+        assert_eq!(from_unsigned_small_float(0x7c0, 6), f32::INFINITY);
+        assert!(from_unsigned_small_float(unsigned_small_float(f32::NAN, 5), 5).is_nan());
+    }
+
+    #[test]
+    fn packs_r11f_g11f_b10f_red_first() {
+        let texel = encode(R11FG11FB10F, ColorData::Float([1.0, 2.0, 0.5, 0.0]));
+        assert_eq!(word(&texel), 15 << 6 | (16 << 6) << 11 | (14 << 5) << 22);
+        assert_eq!(floats(decode_color(R11FG11FB10F, &texel)), [1.0, 2.0, 0.5, 1.0]);
+        let texel = encode(R11FG11FB10F, ColorData::Float([-1.0, f32::INFINITY, 0.0, 0.0]));
+        assert_eq!(floats(decode_color(R11FG11FB10F, &texel)), [0.0, f32::INFINITY, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn keeps_float32_special_values() {
+        let color = floats(round_trip(Rgba32F, ColorData::Float([f32::INFINITY, f32::NEG_INFINITY, -0.0, f32::MIN_POSITIVE / 2.0])));
+        assert_eq!(color[..2], [f32::INFINITY, f32::NEG_INFINITY]);
+        assert!(color[2] == 0.0 && color[2].is_sign_negative());
+        assert_eq!(color[3], f32::MIN_POSITIVE / 2.0);
+        assert!(floats(round_trip(R32F, ColorData::Float([f32::NAN, 0.0, 0.0, 0.0])))[0].is_nan());
+    }
+
+    #[test]
+    fn keeps_half_float_components_signed() {
+        let color = floats(round_trip(Rgba16F, ColorData::Float([-1.5, 0.1, f32::NEG_INFINITY, 70000.0])));
+        assert_eq!(color, [-1.5, from_half_float(half_float(0.1)), f32::NEG_INFINITY, f32::INFINITY]);
+    }
+}
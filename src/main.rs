@@ -2,10 +2,11 @@ use std::{thread, time::Duration};
 
 use sdl3::{
     self,
+    event::WindowEvent,
     pixels::{PixelFormat, PixelFormatEnum},
 };
 
-use crate::{context::with_current_context, enums::{ClearBufferMask, DrawBufferSys}, renderer::{glBindFramebuffer, glBlitFramebuffer, glClear, glClearColor, glDrawBuffer, glDrawBuffers, glGenFramebuffers}, KoriExt::{glKCreateContext, glKSwapBuffers}};
+use crate::{context::with_current_context, enums::{ClearBufferMask, DrawBufferSys}, renderer::{glBindFramebuffer, glBlitFramebuffer, glClear, glClearColor, glDrawBuffer, glDrawBuffers, glGenFramebuffers}, KoriExt::{glKCreateContext, glKDestroyContext, glKMakeCurrent, glKResizeSurface, glKSwapBuffers}};
 mod renderer;
 mod states;
 mod types;
@@ -13,6 +14,16 @@ mod enums;
 #[allow(non_snake_case)]
 mod KoriExt;
 mod context;
+mod texture;
+mod renderbuffer;
+mod shader;
+mod query;
+mod capability;
+mod pixels;
+mod format;
+mod buffer;
+mod vertex;
+mod draw;
 
 fn main() {
     const WINDOW_WIDTH: usize = 800;
@@ -35,7 +46,9 @@ fn main() {
             WINDOW_HEIGHT as u32,
         )
         .unwrap();
-    glKCreateContext(WINDOW_WIDTH, WINDOW_HEIGHT, 0, 1);
+    let mut surface_width = WINDOW_WIDTH;
+    let context = glKCreateContext(WINDOW_WIDTH, WINDOW_HEIGHT, 0, 1);
+    glKMakeCurrent(context);
     //glDrawBuffers(1, [0x405u32; 1].as_mut_ptr());
     let mut cmask = 1u8;
     let mut test_fbo: [u32; 1] = [0u32];
@@ -43,6 +56,17 @@ fn main() {
         for event in event_pump.poll_iter() {
             match event {
                 sdl3::event::Event::Quit { .. } => break 'running,
+                sdl3::event::Event::Window { win_event: WindowEvent::PixelSizeChanged(width, height), .. } if width > 0 && height > 0 => {
+                    surface_width = width as usize;
+                    glKResizeSurface(surface_width, height as usize, 1);
+                    texture = texture_creator
+                        .create_texture_streaming(
+                            unsafe { PixelFormat::from_ll(PixelFormatEnum::RGBA8888.to_ll()) },
+                            width as u32,
+                            height as u32,
+                        )
+                        .unwrap();
+                }
                 _ => {}
             }
         }
@@ -54,11 +78,13 @@ fn main() {
         glClear(ClearBufferMask::COLOR as u32);
         glKSwapBuffers();
         texture
-            .update(None, &with_current_context(|ctx| ctx.default_framebuffer.as_slice_u8(DrawBufferSys::FrontLeft)), (WINDOW_WIDTH * 4) as usize)
+            .update(None, &with_current_context(|ctx| ctx.default_framebuffer.as_slice_u8(DrawBufferSys::FrontLeft)).unwrap(), surface_width * 4)
             .unwrap();
         canvas.clear();
         canvas.copy(&texture, None, None).unwrap();
         canvas.present();
         thread::sleep(Duration::from_millis(250));
     }
+    glKMakeCurrent(0);
+    glKDestroyContext(context);
 }
@@ -0,0 +1,507 @@
+use std::slice;
+
+use crate::{
+    context::{with_current_context, GlContext},
+    buffer::bound_buffer_object,
+    enums::{BufferTarget, ErrorCode, Framebuffer, PixelFormat, PixelStoreParameter, PixelType},
+    format::{half_float, unorm, unsigned_small_float, write_bits},
+    renderer::{check_framebuffer_complete, framebuffer_samples, with_color_buffer, with_depth_plane, with_stencil_plane, ColorSelect},
+    states::PixelStoreState,
+    types::{sample_count, GlSizei, Rect},
+};
+
+// Whether `parameter` belongs to the pack or the unpack state
+fn pixel_store_state(context: &mut GlContext, parameter: PixelStoreParameter) -> &mut PixelStoreState {
+    match parameter {
+        PixelStoreParameter::PackSwapBytes
+        | PixelStoreParameter::PackLsbFirst
+        | PixelStoreParameter::PackRowLength
+        | PixelStoreParameter::PackSkipRows
+        | PixelStoreParameter::PackSkipPixels
+        | PixelStoreParameter::PackAlignment
+        | PixelStoreParameter::PackSkipImages
+        | PixelStoreParameter::PackImageHeight => &mut context.pack_state,
+        _ => &mut context.unpack_state,
+    }
+}
+
+pub(crate) fn get_pixel_store(context: &mut GlContext, parameter: PixelStoreParameter) -> i32 {
+    let state = pixel_store_state(context, parameter);
+    match parameter {
+        PixelStoreParameter::PackSwapBytes | PixelStoreParameter::UnpackSwapBytes => state.swap_bytes as i32,
+        PixelStoreParameter::PackLsbFirst | PixelStoreParameter::UnpackLsbFirst => state.lsb_first as i32,
+        PixelStoreParameter::PackRowLength | PixelStoreParameter::UnpackRowLength => state.row_length,
+        PixelStoreParameter::PackSkipRows | PixelStoreParameter::UnpackSkipRows => state.skip_rows,
+        PixelStoreParameter::PackSkipPixels | PixelStoreParameter::UnpackSkipPixels => state.skip_pixels,
+        PixelStoreParameter::PackAlignment | PixelStoreParameter::UnpackAlignment => state.alignment,
+        PixelStoreParameter::PackImageHeight | PixelStoreParameter::UnpackImageHeight => state.image_height,
+        PixelStoreParameter::PackSkipImages | PixelStoreParameter::UnpackSkipImages => state.skip_images,
+    }
+}
+
+fn pixel_store(context: &mut GlContext, pname: u32, param: i32) {
+    let Some(parameter) = PixelStoreParameter::from_u32(pname) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    let is_alignment = matches!(parameter, PixelStoreParameter::PackAlignment | PixelStoreParameter::UnpackAlignment);
+    if param < 0 || is_alignment && !matches!(param, 1 | 2 | 4 | 8) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let state = pixel_store_state(context, parameter);
+    match parameter {
+        PixelStoreParameter::PackSwapBytes | PixelStoreParameter::UnpackSwapBytes => state.swap_bytes = param != 0,
+        PixelStoreParameter::PackLsbFirst | PixelStoreParameter::UnpackLsbFirst => state.lsb_first = param != 0,
+        PixelStoreParameter::PackRowLength | PixelStoreParameter::UnpackRowLength => state.row_length = param,
+        PixelStoreParameter::PackSkipRows | PixelStoreParameter::UnpackSkipRows => state.skip_rows = param,
+        PixelStoreParameter::PackSkipPixels | PixelStoreParameter::UnpackSkipPixels => state.skip_pixels = param,
+        PixelStoreParameter::PackAlignment | PixelStoreParameter::UnpackAlignment => state.alignment = param,
+        PixelStoreParameter::PackImageHeight | PixelStoreParameter::UnpackImageHeight => state.image_height = param,
+        PixelStoreParameter::PackSkipImages | PixelStoreParameter::UnpackSkipImages => state.skip_images = param,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glPixelStorei(pname: u32, param: i32) {
+    with_current_context(|context| pixel_store(context, pname, param));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glPixelStoref(pname: u32, param: f32) {
+    with_current_context(|context| pixel_store(context, pname, param.round() as i32));
+}
+
+// Byte layout of a rectangle of pixels in application memory
+#[derive(Debug, Clone, Copy)]
+struct PixelLayout {
+    // Bytes per pixel
+    pixel_size: usize,
+    // Bytes between the start of two rows, including alignment padding
+    row_stride: usize,
+    // Offset of the first pixel after skipping rows and pixels
+    offset: usize,
+}
+
+impl PixelLayout {
+    // None when the layout doesn't fit in the address space
+    fn new(state: &PixelStoreState, format: PixelFormat, pixel_type: PixelType, width: usize) -> Option<Self> {
+        let element_size = pixel_type.size();
+        let pixel_size = match pixel_type.packed_components() {
+            Some(_) => element_size,
+            None => element_size * format.component_count(),
+        };
+        let row_length = if state.row_length > 0 { state.row_length as usize } else { width };
+        let alignment = state.alignment as usize;
+        // Rows are only padded when elements are smaller than the alignment
+        let row_size = row_length.checked_mul(pixel_size)?;
+        let row_stride = if element_size >= alignment {
+            row_size
+        } else {
+            row_size.div_ceil(alignment).checked_mul(alignment)?
+        };
+        let offset = (state.skip_rows as usize)
+            .checked_mul(row_stride)?
+            .checked_add((state.skip_pixels as usize).checked_mul(pixel_size)?)?;
+        Some(Self { pixel_size, row_stride, offset })
+    }
+
+    // Bytes touched when `width` x `height` pixels are transferred, None when that doesn't fit in the address space
+    fn required_size(&self, width: usize, height: usize) -> Option<usize> {
+        if width == 0 || height == 0 {
+            return Some(0);
+        }
+        self.offset
+            .checked_add((height - 1).checked_mul(self.row_stride)?)?
+            .checked_add(width.checked_mul(self.pixel_size)?)
+    }
+}
+
+// Formats and types that can be combined. Packed types need exactly as many components as they store.
+fn is_valid_combination(format: PixelFormat, pixel_type: PixelType) -> bool {
+    match (format, pixel_type) {
+        (PixelFormat::DepthStencil, PixelType::UnsignedInt248 | PixelType::Float32UnsignedInt248Rev) => true,
+        (PixelFormat::DepthStencil, _) | (_, PixelType::UnsignedInt248 | PixelType::Float32UnsignedInt248Rev) => false,
+        // Shared exponent and packed float types only come in RGB order
+        (PixelFormat::Rgb, PixelType::UnsignedInt10F11F11FRev | PixelType::UnsignedInt5999Rev) => true,
+        (_, PixelType::UnsignedInt10F11F11FRev | PixelType::UnsignedInt5999Rev) => false,
+        (PixelFormat::StencilIndex | PixelFormat::DepthComponent, _) => pixel_type.packed_components().is_none(),
+        // Integer components are never converted to floats
+        (_, PixelType::Float | PixelType::HalfFloat) if format.is_integer() => false,
+        (_, _) => pixel_type.packed_components().is_none_or(|components| components == format.component_count()),
+    }
+}
+
+// How values are converted to the integer pixel types
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Conversion {
+    // Colors and depth values in [0, 1], or [-1, 1] for signed types
+    Normalized,
+    // Stencil indices, converted as is
+    Integer,
+}
+
+fn snorm(value: f64, bits: u32) -> u64 {
+    let max = ((1u64 << (bits - 1)) - 1) as f64;
+    let value = (value.clamp(-1.0, 1.0) * max).round() as i64;
+    value as u64 & ((1u64 << bits) - 1)
+}
+
+// Encodes RGB with a shared five bit exponent and nine bit mantissas, see EXT_texture_shared_exponent
+fn shared_exponent(red: f64, green: f64, blue: f64) -> u64 {
+    const MANTISSA_BITS: i32 = 9;
+    const BIAS: i32 = 15;
+    let max_value = 511.0 / 512.0 * 2f64.powi(31 - BIAS);
+    let [red, green, blue] = [red, green, blue].map(|value| {
+        if value.is_nan() { 0.0 } else { value.clamp(0.0, max_value) }
+    });
+    let max_component = red.max(green).max(blue);
+    let mut exponent = (max_component.log2().floor() as i32).max(-BIAS - 1) + 1 + BIAS;
+    if (max_component / 2f64.powi(exponent - BIAS - MANTISSA_BITS) + 0.5).floor() as i32 == 1 << MANTISSA_BITS {
+        exponent += 1;
+    }
+    let scale = 2f64.powi(exponent - BIAS - MANTISSA_BITS);
+    let [red, green, blue] = [red, green, blue].map(|value| (value / scale + 0.5).floor() as u64);
+    red | (green << 9) | (blue << 18) | ((exponent as u64) << 27)
+}
+
+// Writes the lowest `size` bytes of `bits` in native byte order, swapped with PACK_SWAP_BYTES
+fn write_element(out: &mut [u8], bits: u64, size: usize, swap_bytes: bool) {
+    write_bits(out, bits, size);
+    if swap_bytes {
+        out[..size].reverse();
+    }
+}
+
+// Bit widths of the fields of a packed type, starting at the field holding the first component
+fn packed_fields(pixel_type: PixelType) -> (&'static [u32], bool) {
+    // The second value tells whether the first component is stored in the least significant bits
+    match pixel_type {
+        PixelType::UnsignedByte332 => (&[3, 3, 2], false),
+        PixelType::UnsignedByte233Rev => (&[3, 3, 2], true),
+        PixelType::UnsignedShort565 => (&[5, 6, 5], false),
+        PixelType::UnsignedShort565Rev => (&[5, 6, 5], true),
+        PixelType::UnsignedShort4444 => (&[4, 4, 4, 4], false),
+        PixelType::UnsignedShort4444Rev => (&[4, 4, 4, 4], true),
+        PixelType::UnsignedShort5551 => (&[5, 5, 5, 1], false),
+        PixelType::UnsignedShort1555Rev => (&[5, 5, 5, 1], true),
+        PixelType::UnsignedInt8888 => (&[8, 8, 8, 8], false),
+        PixelType::UnsignedInt8888Rev => (&[8, 8, 8, 8], true),
+        PixelType::UnsignedInt1010102 => (&[10, 10, 10, 2], false),
+        PixelType::UnsignedInt2101010Rev => (&[10, 10, 10, 2], true),
+        _ => (&[], false),
+    }
+}
+
+// Encodes one pixel whose components are given in the order of the pixel format
+fn encode_pixel(components: &[f64], pixel_type: PixelType, conversion: Conversion, out: &mut [u8], swap_bytes: bool) {
+    let size = pixel_type.size();
+    let bits = match pixel_type {
+        PixelType::Float32UnsignedInt248Rev => {
+            // A float depth value followed by a 32 bit word holding the stencil index in its lowest bits
+            write_element(out, (components[0] as f32).to_bits() as u64, 4, swap_bytes);
+            write_element(&mut out[4..], components[1] as u64 & 0xff, 4, swap_bytes);
+            return;
+        }
+        PixelType::UnsignedInt248 => (unorm(components[0], 24) << 8) | (components[1] as u64 & 0xff),
+        PixelType::UnsignedInt10F11F11FRev => {
+            unsigned_small_float(components[0] as f32, 6)
+                | (unsigned_small_float(components[1] as f32, 6) << 11)
+                | (unsigned_small_float(components[2] as f32, 5) << 22)
+        }
+        PixelType::UnsignedInt5999Rev => shared_exponent(components[0], components[1], components[2]),
+        _ if pixel_type.packed_components().is_some() => {
+            let (fields, reversed) = packed_fields(pixel_type);
+            let mut bits = 0;
+            let mut shift = if reversed { 0 } else { fields.iter().sum() };
+            for (&component, &width) in components.iter().zip(fields) {
+                if reversed {
+                    bits |= unorm(component, width) << shift;
+                    shift += width;
+                } else {
+                    shift -= width;
+                    bits |= unorm(component, width) << shift;
+                }
+            }
+            bits
+        }
+        _ => {
+            for (index, &component) in components.iter().enumerate() {
+                let bits = match (pixel_type, conversion) {
+                    (PixelType::Float, _) => (component as f32).to_bits() as u64,
+                    (PixelType::HalfFloat, _) => half_float(component as f32),
+                    (PixelType::UnsignedByte | PixelType::UnsignedShort | PixelType::UnsignedInt, Conversion::Normalized) => {
+                        unorm(component, size as u32 * 8)
+                    }
+                    (_, Conversion::Normalized) => snorm(component, size as u32 * 8),
+                    (PixelType::UnsignedByte | PixelType::UnsignedShort | PixelType::UnsignedInt, Conversion::Integer) => {
+                        (component.max(0.0) as u64).min((1 << (size * 8)) - 1)
+                    }
+                    (_, Conversion::Integer) => {
+                        let max = (1i64 << (size * 8 - 1)) - 1;
+                        (component as i64).clamp(-max - 1, max) as u64
+                    }
+                };
+                write_element(&mut out[index * size..], bits, size, swap_bytes);
+            }
+            return;
+        }
+    };
+    write_element(out, bits, size, swap_bytes);
+}
+
+// Rectangle read by glReadPixels, after validation
+#[derive(Debug, Clone, Copy)]
+struct ReadRect {
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+}
+
+// Encodes every pixel of a `width` x `height` buffer inside the read rectangle into `data`, passing the index of the
+// pixel to `encode`. Pixels outside of the buffer are left untouched.
+fn pack_pixels((width, height): (usize, usize), read: ReadRect, layout: PixelLayout, data: &mut [u8], encode: impl Fn(usize, &mut [u8])) {
+    let clamp = |value: i64, size: usize| value.clamp(0, size as i64) as usize;
+    let rect = Rect {
+        x0: clamp(read.x as i64, width),
+        y0: clamp(read.y as i64, height),
+        x1: clamp(read.x as i64 + read.width as i64, width),
+        y1: clamp(read.y as i64 + read.height as i64, height),
+    };
+    for y in rect.y0..rect.y1 {
+        let row = layout.offset + (y as i64 - read.y as i64) as usize * layout.row_stride;
+        for x in rect.x0..rect.x1 {
+            let offset = row + (x as i64 - read.x as i64) as usize * layout.pixel_size;
+            encode(y * width + x, &mut data[offset..offset + layout.pixel_size]);
+        }
+    }
+}
+
+// Arguments of glReadPixels and glReadnPixels
+struct ReadPixels {
+    x: i32,
+    y: i32,
+    width: GlSizei,
+    height: GlSizei,
+    format: u32,
+    pixel_type: u32,
+}
+
+// Reads a rectangle of the read framebuffer into `data`, which is `buf_size` bytes long when given.
+// `data` is an offset into the pixel pack buffer when one is bound.
+fn read_pixels(context: &mut GlContext, args: ReadPixels, buf_size: Option<GlSizei>, data: *mut u8) {
+    let ReadPixels { x, y, width, height, format, pixel_type } = args;
+    if width < 0 || height < 0 || buf_size.is_some_and(|size| size < 0) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let (Some(format), Some(pixel_type)) = (PixelFormat::from_u32(format), PixelType::from_u32(pixel_type)) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    if !is_valid_combination(format, pixel_type) {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let framebuffer = context.framebuffer_state.read_framebuffer;
+    if !check_framebuffer_complete(context, framebuffer) {
+        return;
+    }
+    // Multisampled framebuffer objects have to be resolved with a blit first, the default framebuffer is resolved
+    // while reading
+    if matches!(framebuffer, Framebuffer::UserDefined(_)) && framebuffer_samples(context, framebuffer) > 0 {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let has_buffer = match format {
+        PixelFormat::DepthComponent => with_depth_plane(context, framebuffer, |_, _| ()).is_some(),
+        PixelFormat::StencilIndex => with_stencil_plane(context, framebuffer, |_, _| ()).is_some(),
+        PixelFormat::DepthStencil => {
+            with_depth_plane(context, framebuffer, |_, _| ()).is_some()
+                && with_stencil_plane(context, framebuffer, |_, _| ()).is_some()
+        }
+        _ => with_color_buffer(context, framebuffer, ColorSelect::Read, |_| ()).is_some(),
+    };
+    if !has_buffer {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    // Integer buffers can only be read with integer formats and the other way around
+    if !matches!(format, PixelFormat::DepthComponent | PixelFormat::StencilIndex | PixelFormat::DepthStencil)
+        && let Some(component_type) = with_color_buffer(context, framebuffer, ColorSelect::Read, |buffer| buffer.format.component_type())
+        && component_type.is_integer() != format.is_integer()
+    {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let (width, height) = (width as usize, height as usize);
+    let Some((layout, required_size)) = PixelLayout::new(&context.pack_state, format, pixel_type, width)
+        .and_then(|layout| Some((layout, layout.required_size(width, height)?)))
+    else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    };
+    // glReadnPixels never writes past the end of the buffer
+    if buf_size.is_some_and(|size| (size as usize) < required_size) {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    // With a buffer bound to GL_PIXEL_PACK_BUFFER `data` is an offset into that buffer
+    let pack_buffer = bound_buffer_object(context, BufferTarget::PixelPack);
+    let mut pack_buffer = pack_buffer.as_ref().map(|buffer| buffer.lock().unwrap());
+    let data = match &mut pack_buffer {
+        Some(buffer) => {
+            let offset = data as usize;
+            if offset.checked_add(required_size).is_none_or(|end| end > buffer.data.len()) || buffer.is_mapped_exclusively() {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
+            }
+            &mut buffer.data[offset..offset + required_size]
+        }
+        None if data.is_null() || required_size == 0 => return,
+        None => unsafe { slice::from_raw_parts_mut(data, required_size) },
+    };
+    let read = ReadRect { x, y, width, height };
+    let swap_bytes = context.pack_state.swap_bytes;
+    match format {
+        PixelFormat::DepthComponent => {
+            with_depth_plane(context, framebuffer, |plane, _| {
+                pack_pixels((plane.width, plane.height), read, layout, data, |index, out| {
+                    let depth = plane.pixels[index * sample_count(plane.samples)];
+                    encode_pixel(&[depth as f64], pixel_type, Conversion::Normalized, out, swap_bytes)
+                });
+            });
+        }
+        PixelFormat::StencilIndex => {
+            with_stencil_plane(context, framebuffer, |plane, _| {
+                pack_pixels((plane.width, plane.height), read, layout, data, |index, out| {
+                    let stencil = plane.pixels[index * sample_count(plane.samples)];
+                    encode_pixel(&[stencil as f64], pixel_type, Conversion::Integer, out, swap_bytes)
+                });
+            });
+        }
+        PixelFormat::DepthStencil => {
+            // Depth and stencil may share an image, so both are copied out before the pixels are encoded together.
+            // Multisampled buffers read the first sample of each pixel.
+            let Some((depth, width, height, samples)) = with_depth_plane(context, framebuffer, |plane, _| {
+                (plane.pixels.to_vec(), plane.width, plane.height, plane.samples)
+            }) else {
+                return;
+            };
+            let Some(stencil) = with_stencil_plane(context, framebuffer, |plane, _| plane.pixels.to_vec()) else {
+                return;
+            };
+            let count = sample_count(samples);
+            pack_pixels((width, height), read, layout, data, |index, out| {
+                let components = [depth[index * count] as f64, stencil[index * count] as f64];
+                encode_pixel(&components, pixel_type, Conversion::Integer, out, swap_bytes)
+            });
+        }
+        _ => {
+            let conversion = if format.is_integer() { Conversion::Integer } else { Conversion::Normalized };
+            with_color_buffer(context, framebuffer, ColorSelect::Read, |buffer| {
+                pack_pixels((buffer.width, buffer.height), read, layout, data, |index, out| {
+                    // Samples are averaged as stored, without decoding sRGB
+                    let [red, green, blue, alpha] = buffer.resolve(index, false).components();
+                    let components = match format {
+                        PixelFormat::Red | PixelFormat::RedInteger => [red, 0.0, 0.0, 0.0],
+                        PixelFormat::Green | PixelFormat::GreenInteger => [green, 0.0, 0.0, 0.0],
+                        PixelFormat::Blue | PixelFormat::BlueInteger => [blue, 0.0, 0.0, 0.0],
+                        PixelFormat::Alpha => [alpha, 0.0, 0.0, 0.0],
+                        PixelFormat::Rg | PixelFormat::RgInteger => [red, green, 0.0, 0.0],
+                        PixelFormat::Rgb | PixelFormat::RgbInteger => [red, green, blue, 0.0],
+                        PixelFormat::Bgr | PixelFormat::BgrInteger => [blue, green, red, 0.0],
+                        PixelFormat::Bgra | PixelFormat::BgraInteger => [blue, green, red, alpha],
+                        _ => [red, green, blue, alpha],
+                    };
+                    let count = format.component_count();
+                    encode_pixel(&components[..count], pixel_type, conversion, out, swap_bytes);
+                });
+            });
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glReadPixels(x: i32, y: i32, width: GlSizei, height: GlSizei, format: u32, pixel_type: u32, data: *mut u8) {
+    let args = ReadPixels { x, y, width, height, format, pixel_type };
+    with_current_context(|context| read_pixels(context, args, None, data));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glReadnPixels(x: i32, y: i32, width: GlSizei, height: GlSizei, format: u32, pixel_type: u32, buf_size: GlSizei, data: *mut u8) {
+    let args = ReadPixels { x, y, width, height, format, pixel_type };
+    with_current_context(|context| read_pixels(context, args, Some(buf_size), data));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(state: PixelStoreState, format: PixelFormat, pixel_type: PixelType, width: usize) -> PixelLayout {
+        PixelLayout::new(&state, format, pixel_type, width).unwrap()
+    }
+
+    #[test]
+    fn pads_rows_to_alignment() {
+        let state = PixelStoreState::default();
+        let rgb = layout(state, PixelFormat::Rgb, PixelType::UnsignedByte, 3);
+        assert_eq!((rgb.pixel_size, rgb.row_stride, rgb.offset), (3, 12, 0));
+        // The last row isn't padded
+        assert_eq!(rgb.required_size(3, 2), Some(21));
+        let unaligned = layout(PixelStoreState { alignment: 1, ..state }, PixelFormat::Rgb, PixelType::UnsignedByte, 3);
+        assert_eq!(unaligned.row_stride, 9);
+        let wide = layout(PixelStoreState { alignment: 8, ..state }, PixelFormat::Rgb, PixelType::UnsignedByte, 3);
+        assert_eq!(wide.row_stride, 16);
+        let float = layout(PixelStoreState { alignment: 8, ..state }, PixelFormat::Rgb, PixelType::Float, 1);
+        assert_eq!(float.row_stride, 16);
+        // Packed types are a single element
+        let packed = layout(state, PixelFormat::Rgb, PixelType::UnsignedShort565, 3);
+        assert_eq!((packed.pixel_size, packed.row_stride), (2, 8));
+    }
+
+    #[test]
+    fn skips_alignment_of_large_elements() {
+        let state = PixelStoreState { alignment: 2, ..PixelStoreState::default() };
+        assert_eq!(layout(state, PixelFormat::Rgb, PixelType::UnsignedShort, 1).row_stride, 6);
+        assert_eq!(layout(state, PixelFormat::Red, PixelType::Float, 3).row_stride, 12);
+    }
+
+    #[test]
+    fn uses_row_length() {
+        let state = PixelStoreState { row_length: 10, ..PixelStoreState::default() };
+        let rgba = layout(state, PixelFormat::Rgba, PixelType::UnsignedByte, 3);
+        assert_eq!(rgba.row_stride, 40);
+        assert_eq!(rgba.required_size(3, 2), Some(52));
+        let rgb = layout(state, PixelFormat::Rgb, PixelType::UnsignedByte, 3);
+        assert_eq!(rgb.row_stride, 32);
+    }
+
+    #[test]
+    fn skips_rows_and_pixels() {
+        let state = PixelStoreState { skip_rows: 2, skip_pixels: 1, ..PixelStoreState::default() };
+        let rgba = layout(state, PixelFormat::Rgba, PixelType::UnsignedByte, 4);
+        assert_eq!(rgba.offset, 36);
+        assert_eq!(rgba.required_size(4, 1), Some(52));
+        assert_eq!(rgba.required_size(4, 3), Some(84));
+    }
+
+    #[test]
+    fn empty_transfers_touch_nothing() {
+        let state = PixelStoreState { skip_rows: 2, ..PixelStoreState::default() };
+        let rgba = layout(state, PixelFormat::Rgba, PixelType::UnsignedByte, 4);
+        assert_eq!(rgba.required_size(0, 3), Some(0));
+        assert_eq!(rgba.required_size(4, 0), Some(0));
+    }
+
+    #[test]
+    fn rejects_overflowing_layouts() {
+        let state = PixelStoreState { row_length: i32::MAX, skip_rows: i32::MAX, ..PixelStoreState::default() };
+        assert!(PixelLayout::new(&state, PixelFormat::Rgba, PixelType::Float, 1).is_none());
+        assert!(PixelLayout::new(&PixelStoreState::default(), PixelFormat::Rgba, PixelType::Float, usize::MAX).is_none());
+        let rgba = layout(PixelStoreState::default(), PixelFormat::Rgba, PixelType::UnsignedByte, 4);
+        assert_eq!(rgba.required_size(usize::MAX, 1), None);
+        assert_eq!(rgba.required_size(4, usize::MAX), None);
+    }
+}
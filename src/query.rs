@@ -0,0 +1,374 @@
+use std::slice;
+
+use crate::{
+    context::{with_current_context, GlContext},
+    pixels::get_pixel_store,
+    renderer::framebuffer_samples,
+    enums::{
+        BufferTarget, Capability, DrawBufferSys, ErrorCode, Framebuffer, GetPName, IndexedBindingPName,
+        PixelStoreParameter, TextureTarget, VertexBindingPName, GL_DRAW_BUFFER0, GL_MAX_ATOMIC_COUNTER_BUFFER_BINDINGS,
+        GL_MAX_COLOR_ATTACHMENTS, GL_MAX_COLOR_TEXTURE_SAMPLES, GL_MAX_DEPTH_TEXTURE_SAMPLES, GL_MAX_DRAW_BUFFERS,
+        GL_MAX_FRAMEBUFFER_HEIGHT, GL_MAX_FRAMEBUFFER_LAYERS, GL_MAX_FRAMEBUFFER_SAMPLES, GL_MAX_FRAMEBUFFER_WIDTH,
+        GL_MAX_INTEGER_SAMPLES, GL_MAX_RENDERBUFFER_SIZE, GL_MAX_SAMPLES, GL_MAX_SHADER_STORAGE_BUFFER_BINDINGS,
+        GL_MAX_TRANSFORM_FEEDBACK_BUFFERS, GL_MAX_UNIFORM_BUFFER_BINDINGS, GL_MAX_VERTEX_ATTRIBS,
+        GL_MAX_VERTEX_ATTRIB_BINDINGS, GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET, GL_MAX_VERTEX_ATTRIB_STRIDE,
+        GL_MAX_VIEWPORT_DIMS, GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT, GL_SUBPIXEL_BITS,
+        GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT,
+    },
+    types::GlBool,
+};
+
+// Queried state in the type it is stored as. glGet* converts it to the requested type.
+enum StateValue {
+    Booleans(Vec<bool>),
+    Integers(Vec<i64>),
+    // Colors and depth values, mapped onto [-i32::MAX; i32::MAX] when queried as integers
+    NormalizedFloats(Vec<f64>),
+}
+
+impl StateValue {
+    fn as_booleans(&self) -> Vec<GlBool> {
+        match self {
+            Self::Booleans(values) => values.iter().map(|&value| value as GlBool).collect(),
+            Self::Integers(values) => values.iter().map(|&value| (value != 0) as GlBool).collect(),
+            Self::NormalizedFloats(values) => {
+                values.iter().map(|&value| (value != 0.0) as GlBool).collect()
+            }
+        }
+    }
+
+    fn as_integers(&self) -> Vec<i64> {
+        match self {
+            Self::Booleans(values) => values.iter().map(|&value| value as i64).collect(),
+            Self::Integers(values) => values.clone(),
+            Self::NormalizedFloats(values) => values
+                .iter()
+                .map(|&value| (value.clamp(-1.0, 1.0) * i32::MAX as f64).round() as i64)
+                .collect(),
+        }
+    }
+
+    // Integers saturated to the range of GLint
+    fn as_clamped_integers(&self) -> Vec<i32> {
+        self.as_integers()
+            .into_iter()
+            .map(|value| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+            .collect()
+    }
+
+    fn as_floats(&self) -> Vec<f64> {
+        match self {
+            Self::Booleans(values) => values.iter().map(|&value| value as u8 as f64).collect(),
+            Self::Integers(values) => values.iter().map(|&value| value as f64).collect(),
+            Self::NormalizedFloats(values) => values.clone(),
+        }
+    }
+}
+
+fn framebuffer_name(framebuffer: Framebuffer) -> i64 {
+    match framebuffer {
+        Framebuffer::Default => 0,
+        Framebuffer::UserDefined(fbo_id) => fbo_id as i64,
+    }
+}
+
+fn texture_binding(context: &GlContext, target: TextureTarget) -> StateValue {
+    StateValue::Integers(vec![context.texture_bindings.get(&target).copied().unwrap_or(0) as i64])
+}
+
+fn draw_buffer(context: &GlContext, index: usize) -> StateValue {
+    let buffer = match context.framebuffer_state.write_framebuffer {
+        Framebuffer::Default if index == 0 => context.default_framebuffer.draw_buffer as u32,
+        Framebuffer::Default => DrawBufferSys::NONE as u32,
+        Framebuffer::UserDefined(fbo_id) => context
+            .framebuffer_objects
+            .get(&fbo_id)
+            .and_then(|fbo| fbo.draw_buffers[index])
+            .map_or(0, |attachment| attachment as u32),
+    };
+    StateValue::Integers(vec![buffer as i64])
+}
+
+fn read_buffer(context: &GlContext) -> StateValue {
+    let buffer = match context.framebuffer_state.read_framebuffer {
+        Framebuffer::Default => context.default_framebuffer.read_buffer as u32,
+        Framebuffer::UserDefined(fbo_id) => context
+            .framebuffer_objects
+            .get(&fbo_id)
+            .and_then(|fbo| fbo.read_buffer)
+            .map_or(0, |attachment| attachment as u32),
+    };
+    StateValue::Integers(vec![buffer as i64])
+}
+
+// The central table of everything glGet* can return.
+fn get_state(context: &mut GlContext, pname: u32) -> Option<StateValue> {
+    // Every capability is also queryable as a boolean
+    if let Some(capability) = Capability::from_u32(pname) {
+        return Some(StateValue::Booleans(vec![context.capability_state(capability, 0).get_state()]));
+    }
+    if let Some(parameter) = PixelStoreParameter::from_u32(pname) {
+        return Some(StateValue::Integers(vec![get_pixel_store(context, parameter) as i64]));
+    }
+    if let Some(target) = BufferTarget::from_binding(pname) {
+        return Some(StateValue::Integers(vec![context.buffer_binding(target) as i64]));
+    }
+    if (GL_DRAW_BUFFER0..GL_DRAW_BUFFER0 + GL_MAX_DRAW_BUFFERS as u32).contains(&pname) {
+        return Some(draw_buffer(context, (pname - GL_DRAW_BUFFER0) as usize));
+    }
+    let value = match GetPName::from_u32(pname)? {
+        GetPName::ColorClearValue => {
+            let color = context.clear_state.color_clear_value;
+            StateValue::NormalizedFloats(vec![
+                color.red as f64,
+                color.green as f64,
+                color.blue as f64,
+                color.alpha as f64,
+            ])
+        }
+        GetPName::DepthClearValue => {
+            StateValue::NormalizedFloats(vec![context.clear_state.depth_clear_value as f64])
+        }
+        GetPName::StencilClearValue => {
+            StateValue::Integers(vec![context.clear_state.stenctil_clear_value as i64])
+        }
+        GetPName::ColorWritemask => {
+            let mask = context.color_masks[0];
+            StateValue::Booleans(vec![mask.red, mask.green, mask.blue, mask.alpha])
+        }
+        GetPName::DepthWritemask => StateValue::Booleans(vec![context.depth_state.write_mask]),
+        GetPName::StencilWritemask => {
+            StateValue::Integers(vec![context.stencil_state.front_write_mask as i32 as i64])
+        }
+        GetPName::StencilBackWritemask => {
+            StateValue::Integers(vec![context.stencil_state.back_write_mask as i32 as i64])
+        }
+        GetPName::Viewport => {
+            let viewport = context.viewport;
+            StateValue::Integers(vec![
+                viewport.x as i64,
+                viewport.y as i64,
+                viewport.width as i64,
+                viewport.height as i64,
+            ])
+        }
+        GetPName::ScissorBox => {
+            let scissor = context.scissor;
+            StateValue::Integers(vec![
+                scissor.x as i64,
+                scissor.y as i64,
+                scissor.width as i64,
+                scissor.height as i64,
+            ])
+        }
+        GetPName::DrawFramebufferBinding => StateValue::Integers(vec![framebuffer_name(
+            context.framebuffer_state.write_framebuffer,
+        )]),
+        GetPName::ReadFramebufferBinding => StateValue::Integers(vec![framebuffer_name(
+            context.framebuffer_state.read_framebuffer,
+        )]),
+        GetPName::DrawBuffer => draw_buffer(context, 0),
+        GetPName::ReadBuffer => read_buffer(context),
+        GetPName::Doublebuffer => {
+            StateValue::Booleans(vec![context.default_framebuffer.config.double_buffered])
+        }
+        GetPName::TextureBinding1D => texture_binding(context, TextureTarget::Texture1D),
+        GetPName::TextureBinding2D => texture_binding(context, TextureTarget::Texture2D),
+        GetPName::TextureBinding3D => texture_binding(context, TextureTarget::Texture3D),
+        GetPName::TextureBinding1DArray => texture_binding(context, TextureTarget::Texture1DArray),
+        GetPName::TextureBinding2DArray => texture_binding(context, TextureTarget::Texture2DArray),
+        GetPName::TextureBindingRectangle => {
+            texture_binding(context, TextureTarget::TextureRectangle)
+        }
+        GetPName::TextureBindingCubeMap => texture_binding(context, TextureTarget::TextureCubeMap),
+        GetPName::TextureBindingCubeMapArray => {
+            texture_binding(context, TextureTarget::TextureCubeMapArray)
+        }
+        GetPName::TextureBindingBuffer => texture_binding(context, TextureTarget::TextureBuffer),
+        GetPName::TextureBinding2DMultisample => {
+            texture_binding(context, TextureTarget::Texture2DMultisample)
+        }
+        GetPName::TextureBinding2DMultisampleArray => {
+            texture_binding(context, TextureTarget::Texture2DMultisampleArray)
+        }
+        GetPName::MaxColorAttachments => {
+            StateValue::Integers(vec![GL_MAX_COLOR_ATTACHMENTS as i64])
+        }
+        GetPName::MaxDrawBuffers => StateValue::Integers(vec![GL_MAX_DRAW_BUFFERS as i64]),
+        GetPName::MaxViewportDims => StateValue::Integers(vec![
+            GL_MAX_VIEWPORT_DIMS as i64,
+            GL_MAX_VIEWPORT_DIMS as i64,
+        ]),
+        GetPName::RenderbufferBinding => StateValue::Integers(vec![context.renderbuffer_binding as i64]),
+        GetPName::MaxRenderbufferSize => StateValue::Integers(vec![GL_MAX_RENDERBUFFER_SIZE as i64]),
+        GetPName::MaxFramebufferWidth => StateValue::Integers(vec![GL_MAX_FRAMEBUFFER_WIDTH as i64]),
+        GetPName::MaxFramebufferHeight => StateValue::Integers(vec![GL_MAX_FRAMEBUFFER_HEIGHT as i64]),
+        GetPName::MaxFramebufferLayers => StateValue::Integers(vec![GL_MAX_FRAMEBUFFER_LAYERS as i64]),
+        GetPName::MaxFramebufferSamples => StateValue::Integers(vec![GL_MAX_FRAMEBUFFER_SAMPLES as i64]),
+        GetPName::MaxSamples => StateValue::Integers(vec![GL_MAX_SAMPLES as i64]),
+        GetPName::MaxColorTextureSamples => StateValue::Integers(vec![GL_MAX_COLOR_TEXTURE_SAMPLES as i64]),
+        GetPName::MaxDepthTextureSamples => StateValue::Integers(vec![GL_MAX_DEPTH_TEXTURE_SAMPLES as i64]),
+        GetPName::MaxIntegerSamples => StateValue::Integers(vec![GL_MAX_INTEGER_SAMPLES as i64]),
+        GetPName::VertexArrayBinding => StateValue::Integers(vec![context.vertex_array_binding as i64]),
+        GetPName::MaxVertexAttribs => StateValue::Integers(vec![GL_MAX_VERTEX_ATTRIBS as i64]),
+        GetPName::SubpixelBits => StateValue::Integers(vec![GL_SUBPIXEL_BITS as i64]),
+        GetPName::MaxVertexAttribStride => StateValue::Integers(vec![GL_MAX_VERTEX_ATTRIB_STRIDE as i64]),
+        GetPName::MaxVertexAttribBindings => StateValue::Integers(vec![GL_MAX_VERTEX_ATTRIB_BINDINGS as i64]),
+        GetPName::MaxVertexAttribRelativeOffset => {
+            StateValue::Integers(vec![GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET as i64])
+        }
+        GetPName::MaxUniformBufferBindings => StateValue::Integers(vec![GL_MAX_UNIFORM_BUFFER_BINDINGS as i64]),
+        GetPName::UniformBufferOffsetAlignment => {
+            StateValue::Integers(vec![GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT as i64])
+        }
+        GetPName::MaxTransformFeedbackBuffers => StateValue::Integers(vec![GL_MAX_TRANSFORM_FEEDBACK_BUFFERS as i64]),
+        GetPName::MaxShaderStorageBufferBindings => {
+            StateValue::Integers(vec![GL_MAX_SHADER_STORAGE_BUFFER_BINDINGS as i64])
+        }
+        GetPName::ShaderStorageBufferOffsetAlignment => {
+            StateValue::Integers(vec![GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT as i64])
+        }
+        GetPName::MaxAtomicCounterBufferBindings => {
+            StateValue::Integers(vec![GL_MAX_ATOMIC_COUNTER_BUFFER_BINDINGS as i64])
+        }
+        // Of the framebuffer bound for drawing
+        GetPName::Samples => {
+            StateValue::Integers(vec![framebuffer_samples(context, context.framebuffer_state.write_framebuffer) as i64])
+        }
+        GetPName::SampleBuffers => {
+            StateValue::Integers(vec![(framebuffer_samples(context, context.framebuffer_state.write_framebuffer) > 0) as i64])
+        }
+    };
+    Some(value)
+}
+
+// Writes `value` converted by `convert` into `data`
+fn write_state<T: Copy>(value: &StateValue, data: *mut T, convert: impl FnOnce(&StateValue) -> Vec<T>) {
+    if data.is_null() {
+        return;
+    }
+    let values = convert(value);
+    let data = unsafe { slice::from_raw_parts_mut(data, values.len()) };
+    data.copy_from_slice(&values);
+}
+
+// Looks up `pname` and writes it converted by `convert` into `data`.
+fn get<T: Copy>(pname: u32, data: *mut T, convert: impl FnOnce(&StateValue) -> Vec<T>) {
+    with_current_context(|context| {
+        let Some(value) = get_state(context, pname) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        write_state(&value, data, convert);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBooleanv(pname: u32, data: *mut GlBool) {
+    get(pname, data, StateValue::as_booleans);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetIntegerv(pname: u32, data: *mut i32) {
+    get(pname, data, StateValue::as_clamped_integers);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetInteger64v(pname: u32, data: *mut i64) {
+    get(pname, data, StateValue::as_integers);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetFloatv(pname: u32, data: *mut f32) {
+    get(pname, data, |value| {
+        value.as_floats().into_iter().map(|value| value as f32).collect()
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetDoublev(pname: u32, data: *mut f64) {
+    get(pname, data, StateValue::as_floats);
+}
+
+// The indexed part of the state table, for glGet*i_v. Records the error and returns None for unknown names
+// and out of range indices.
+fn get_indexed_state(context: &mut GlContext, pname: u32, index: u32) -> Option<StateValue> {
+    if let Some(capability) = Capability::from_u32(pname).filter(|capability| capability.indices() != 0) {
+        if index as usize >= capability.indices() {
+            context.set_error(ErrorCode::InvalidValue);
+            return None;
+        }
+        return Some(StateValue::Booleans(vec![context.capability_state(capability, index as usize).get_state()]));
+    }
+    if GetPName::from_u32(pname) == Some(GetPName::ColorWritemask) {
+        let Some(mask) = context.color_masks.get(index as usize).copied() else {
+            context.set_error(ErrorCode::InvalidValue);
+            return None;
+        };
+        return Some(StateValue::Booleans(vec![mask.red, mask.green, mask.blue, mask.alpha]));
+    }
+    if let Some(pname) = VertexBindingPName::from_u32(pname) {
+        return vertex_binding(context, pname, index).map(|value| StateValue::Integers(vec![value]));
+    }
+    buffer_binding(context, pname, index).map(|value| StateValue::Integers(vec![value]))
+}
+
+// Buffer name, start or size of the indexed buffer binding `index`. Bindings made with glBindBufferBase have
+// a start and size of zero.
+fn buffer_binding(context: &mut GlContext, pname: u32, index: u32) -> Option<i64> {
+    let Some(pname) = IndexedBindingPName::from_u32(pname) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    };
+    let (IndexedBindingPName::Binding(target) | IndexedBindingPName::Start(target) | IndexedBindingPName::Size(target)) = pname;
+    if target.binding_points().is_none_or(|count| index >= count) {
+        context.set_error(ErrorCode::InvalidValue);
+        return None;
+    }
+    let binding = context.indexed_buffer_bindings.get(&(target, index));
+    let value = match pname {
+        IndexedBindingPName::Binding(_) => binding.map_or(0, |binding| binding.buffer as i64),
+        IndexedBindingPName::Start(_) => binding.and_then(|binding| binding.size.map(|_| binding.offset as i64)).unwrap_or(0),
+        IndexedBindingPName::Size(_) => binding.and_then(|binding| binding.size).unwrap_or(0) as i64,
+    };
+    Some(value)
+}
+
+// State of the vertex buffer binding `index` of the bound vertex array object
+fn vertex_binding(context: &mut GlContext, pname: VertexBindingPName, index: u32) -> Option<i64> {
+    let Some(binding) = context.vertex_array().bindings.get(index as usize).copied() else {
+        context.set_error(ErrorCode::InvalidValue);
+        return None;
+    };
+    let value = match pname {
+        VertexBindingPName::Divisor => binding.divisor as i64,
+        VertexBindingPName::Offset => binding.offset as i64,
+        VertexBindingPName::Stride => binding.stride as i64,
+        VertexBindingPName::Buffer => binding.buffer as i64,
+    };
+    Some(value)
+}
+
+// Looks up element `index` of `pname` and writes it converted by `convert` into `data`.
+fn get_indexed<T: Copy>(pname: u32, index: u32, data: *mut T, convert: impl FnOnce(&StateValue) -> Vec<T>) {
+    with_current_context(|context| {
+        if let Some(value) = get_indexed_state(context, pname, index) {
+            write_state(&value, data, convert);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetBooleani_v(target: u32, index: u32, data: *mut GlBool) {
+    get_indexed(target, index, data, StateValue::as_booleans);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetIntegeri_v(target: u32, index: u32, data: *mut i32) {
+    get_indexed(target, index, data, StateValue::as_clamped_integers);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetInteger64i_v(target: u32, index: u32, data: *mut i64) {
+    get_indexed(target, index, data, StateValue::as_integers);
+}
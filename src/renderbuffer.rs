@@ -0,0 +1,175 @@
+use std::{
+    slice,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    context::{with_current_context, GlContext},
+    enums::{
+        ErrorCode, Framebuffer, InternalFormat, RenderbufferParameter, GL_MAX_INTEGER_SAMPLES, GL_MAX_RENDERBUFFER_SIZE,
+        GL_MAX_SAMPLES, GL_RENDERBUFFER, GL_RGBA,
+    },
+    types::{can_allocate, GlBool, GlSizei, Image, Renderbuffer},
+};
+
+// Renderbuffer bound to `target`, recording the matching error when there is none
+fn bound_renderbuffer(context: &mut GlContext, target: u32) -> Option<Arc<Mutex<Renderbuffer>>> {
+    if target != GL_RENDERBUFFER {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    }
+    let renderbuffer = context.shared.lock().unwrap().renderbuffers.objects.get(&context.renderbuffer_binding).cloned();
+    if renderbuffer.is_none() {
+        context.set_error(ErrorCode::InvalidOperation);
+    }
+    renderbuffer
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGenRenderbuffers(n: GlSizei, renderbuffers: *mut u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || renderbuffers.is_null() {
+            return;
+        }
+        let renderbuffer_ids = unsafe { slice::from_raw_parts_mut(renderbuffers, n as usize) };
+        let mut shared = context.shared.lock().unwrap();
+        for id in renderbuffer_ids.iter_mut() {
+            *id = shared.renderbuffers.insert(Arc::new(Mutex::new(Renderbuffer::new())));
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteRenderbuffers(n: GlSizei, renderbuffers: *const u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || renderbuffers.is_null() {
+            return;
+        }
+        let renderbuffer_ids = unsafe { slice::from_raw_parts(renderbuffers, n as usize) };
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        for &key in renderbuffer_ids {
+            if shared.renderbuffers.objects.remove(&key).is_none() {
+                continue;
+            }
+            if context.renderbuffer_binding == key {
+                context.renderbuffer_binding = 0;
+            }
+            // Only the framebuffers bound in the current context are detached from, the storage stays alive
+            // in every other framebuffer it is attached to.
+            for framebuffer in [context.framebuffer_state.write_framebuffer, context.framebuffer_state.read_framebuffer] {
+                if let Framebuffer::UserDefined(fbo_id) = framebuffer
+                    && let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id)
+                {
+                    fbo.detach(|attachment| attachment.is_renderbuffer(key));
+                }
+            }
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsRenderbuffer(renderbuffer: u32) -> GlBool {
+    with_current_context(|context| {
+        context.shared.lock().unwrap().renderbuffers.objects.contains_key(&renderbuffer) as GlBool
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindRenderbuffer(target: u32, renderbuffer: u32) {
+    with_current_context(|context| {
+        if target != GL_RENDERBUFFER {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+        if renderbuffer != 0 && !context.shared.lock().unwrap().renderbuffers.objects.contains_key(&renderbuffer) {
+            // Names must come from glGenRenderbuffers
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        context.renderbuffer_binding = renderbuffer;
+    });
+}
+
+// Replaces the storage of the bound renderbuffer. The new image is zeroed.
+fn renderbuffer_storage(context: &mut GlContext, target: u32, samples: GlSizei, internalformat: u32, width: GlSizei, height: GlSizei) {
+    let Some(renderbuffer) = bound_renderbuffer(context, target) else {
+        return;
+    };
+    let Some(format) = InternalFormat::from_u32(internalformat) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    let valid_size = 0..=GL_MAX_RENDERBUFFER_SIZE;
+    if samples < 0 || !valid_size.contains(&width) || !valid_size.contains(&height) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let max_samples = if format.component_type().is_integer() { GL_MAX_INTEGER_SAMPLES } else { GL_MAX_SAMPLES };
+    if samples > max_samples {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let (width, height, samples) = (width as usize, height as usize, samples as u32);
+    if !can_allocate(Image::storage_size(format, width, height, samples)) {
+        context.set_error(ErrorCode::OutOfMemory);
+        return;
+    }
+    let mut renderbuffer = renderbuffer.lock().unwrap();
+    renderbuffer.internal_format = Some(format);
+    renderbuffer.width = width;
+    renderbuffer.height = height;
+    renderbuffer.samples = samples;
+    renderbuffer.image = Some(Image::new(format, width, height, samples));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glRenderbufferStorage(target: u32, internalformat: u32, width: GlSizei, height: GlSizei) {
+    with_current_context(|context| renderbuffer_storage(context, target, 0, internalformat, width, height));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glRenderbufferStorageMultisample(target: u32, samples: GlSizei, internalformat: u32, width: GlSizei, height: GlSizei) {
+    with_current_context(|context| renderbuffer_storage(context, target, samples, internalformat, width, height));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetRenderbufferParameteriv(target: u32, pname: u32, params: *mut i32) {
+    with_current_context(|context| {
+        let Some(renderbuffer) = bound_renderbuffer(context, target) else {
+            return;
+        };
+        let Some(pname) = RenderbufferParameter::from_u32(pname) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let renderbuffer = renderbuffer.lock().unwrap();
+        let component_sizes = renderbuffer.internal_format.map_or([0; 6], |format| format.component_sizes());
+        let value = match pname {
+            RenderbufferParameter::Samples => renderbuffer.samples as i32,
+            RenderbufferParameter::Width => renderbuffer.width as i32,
+            RenderbufferParameter::Height => renderbuffer.height as i32,
+            RenderbufferParameter::InternalFormat => {
+                renderbuffer.internal_format.map_or(GL_RGBA, |format| format as u32) as i32
+            }
+            RenderbufferParameter::RedSize => component_sizes[0] as i32,
+            RenderbufferParameter::GreenSize => component_sizes[1] as i32,
+            RenderbufferParameter::BlueSize => component_sizes[2] as i32,
+            RenderbufferParameter::AlphaSize => component_sizes[3] as i32,
+            RenderbufferParameter::DepthSize => renderbuffer.internal_format.map_or(0, |format| format.depth_bits()) as i32,
+            RenderbufferParameter::StencilSize => renderbuffer.internal_format.map_or(0, |format| format.stencil_bits()) as i32,
+        };
+        if !params.is_null() {
+            unsafe { *params = value };
+        }
+    });
+}
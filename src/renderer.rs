@@ -1,12 +1,19 @@
-use std::slice;
+use std::{array, mem, slice};
 
 use crate::{
-    context::with_current_context,
+    context::{with_current_context, GlContext, Viewport},
     enums::{
-        ClearBufferMask, DrawBufferFBO, DrawBufferSys, Framebuffer, FramebufferTypes,
-        GL_MAX_COLOR_ATTACHMENTS,
+        AttachmentPoint, Capability, ClearBuffer, ClearBufferMask, DrawBufferFBO, DrawBufferSys, ErrorCode, Face, Filter,
+        Framebuffer, FramebufferParameter, FramebufferStatus, FramebufferTypes, TextureTarget, GL_MAX_3D_TEXTURE_SIZE,
+        GL_MAX_ARRAY_TEXTURE_LAYERS, GL_MAX_COLOR_ATTACHMENTS, GL_MAX_DRAW_BUFFERS, GL_MAX_FRAMEBUFFER_HEIGHT,
+        GL_MAX_FRAMEBUFFER_LAYERS, GL_MAX_FRAMEBUFFER_SAMPLES, GL_MAX_FRAMEBUFFER_WIDTH, GL_MAX_TEXTURE_SIZE,
+        GL_MAX_VIEWPORT_DIMS, GL_RENDERBUFFER, GL_TEXTURE_CUBE_MAP_NEGATIVE_Z, GL_TEXTURE_CUBE_MAP_POSITIVE_X,
+    },
+    states::ColorMask,
+    types::{
+        self, quantize_depth, sample_count, Attachment, ColorBuffer, ColorData, ColorValue, FBO, GlBitfield, GlBool, GlSizei,
+        Image, Rect, Scissor,
     },
-    types::{self, ColorValue, FBO, GlBitfield, GlSizei},
 };
 
 #[unsafe(no_mangle)]
@@ -17,214 +24,620 @@ pub extern "C" fn glClearColor(red: f32, green: f32, blue: f32, alpha: f32) {
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn glClear(mask: types::GlBitfield) {
-    with_current_context(
-        |context| match context.framebuffer_state.write_framebuffer {
-            Framebuffer::Default => match mask {
-                n if ClearBufferMask::COLOR as u32 & n != 0 => {
-                    match context.default_framebuffer.draw_buffer {
-                        crate::enums::DrawBufferSys::Front
-                        | crate::enums::DrawBufferSys::FrontLeft => {
-                            context
-                                .default_framebuffer
-                                .color_buffer_front
-                                .pixels
-                                .fill(context.clear_state.color_clear_value);
-                        }
-                        crate::enums::DrawBufferSys::Back
-                        | crate::enums::DrawBufferSys::BackLeft => {
-                            context
-                                .default_framebuffer
-                                .color_buffer_back
-                                .pixels
-                                .fill(context.clear_state.color_clear_value);
-                        }
-                        crate::enums::DrawBufferSys::NONE => {}
-                        _ => panic!("Unimplemented"),
-                    }
-                }
-                n if ClearBufferMask::DEPTH as u32 & n != 0 => {
-                    if let Some(depth_buffer) = &mut context.default_framebuffer.depth_buffer {
-                        depth_buffer
-                            .pixels
-                            .fill(context.clear_state.depth_clear_value);
-                    }
-                }
-                n if ClearBufferMask::STENCIL as u32 & n != 0 => {
-                    if let Some(stencil_buffer) = &mut context.default_framebuffer.stencil_buffer {
-                        stencil_buffer
-                            .pixels
-                            .fill(context.clear_state.stenctil_clear_value);
-                    }
-                }
-                _ => panic!(),
-            },
-            Framebuffer::UserDefined(fbo_id) => {
-                let active_framebuffer = context.framebuffer_objects.get_mut(&fbo_id).unwrap();
-                for buf in active_framebuffer.draw_buffers.iter() {
-                    if let Some(buffer) = buf {
-                        let index = buffer.get_attachment_index() as usize;
-                        active_framebuffer.color_attachments[index]
-                            .pixels
-                            .fill(context.clear_state.color_clear_value);
-                    }
-                }
-            }
-        },
-    );
+pub extern "C" fn glViewport(x: i32, y: i32, width: GlSizei, height: GlSizei) {
+    with_current_context(|context| {
+        if width < 0 || height < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        context.viewport = Viewport {
+            x,
+            y,
+            width: width.min(GL_MAX_VIEWPORT_DIMS),
+            height: height.min(GL_MAX_VIEWPORT_DIMS),
+        };
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glScissor(x: i32, y: i32, width: GlSizei, height: GlSizei) {
+    with_current_context(|context| {
+        if width < 0 || height < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let enabled = context.scissor.enabled;
+        context.scissor = Scissor::new(x, y, width, height);
+        context.scissor.enabled = enabled;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearDepth(depth: f64) {
+    with_current_context(|context| {
+        context.clear_state.depth_clear_value = depth.clamp(0.0, 1.0) as f32;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearDepthf(depth: f32) {
+    with_current_context(|context| {
+        context.clear_state.depth_clear_value = depth.clamp(0.0, 1.0);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearStencil(s: i32) {
+    with_current_context(|context| {
+        // Masked to the number of stencil bits
+        context.clear_state.stenctil_clear_value = s as u8;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glColorMask(red: GlBool, green: GlBool, blue: GlBool, alpha: GlBool) {
+    with_current_context(|context| {
+        context.color_masks.fill(ColorMask {
+            red: red != 0,
+            green: green != 0,
+            blue: blue != 0,
+            alpha: alpha != 0,
+        });
+    });
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn glDrawBuffers(n: i32, bufs: *mut u32) {
-    if n < 0 {
-        return; // TODO: GL_ERROR GL_INVALID_VALUE
+pub extern "C" fn glColorMaski(buf: u32, red: GlBool, green: GlBool, blue: GlBool, alpha: GlBool) {
+    with_current_context(|context| {
+        if buf as usize >= GL_MAX_DRAW_BUFFERS {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        context.color_masks[buf as usize] = ColorMask {
+            red: red != 0,
+            green: green != 0,
+            blue: blue != 0,
+            alpha: alpha != 0,
+        };
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDepthMask(flag: GlBool) {
+    with_current_context(|context| {
+        context.depth_state.write_mask = flag != 0;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glStencilMask(mask: u32) {
+    with_current_context(|context| {
+        context.stencil_state.front_write_mask = mask;
+        context.stencil_state.back_write_mask = mask;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glStencilMaskSeparate(face: u32, mask: u32) {
+    with_current_context(|context| match Face::from_u32(face) {
+        Some(Face::Front) => context.stencil_state.front_write_mask = mask,
+        Some(Face::Back) => context.stencil_state.back_write_mask = mask,
+        Some(Face::FrontAndBack) => {
+            context.stencil_state.front_write_mask = mask;
+            context.stencil_state.back_write_mask = mask;
+        }
+        None => context.set_error(ErrorCode::InvalidEnum),
+    });
+}
+
+// Clears the color buffer written by `draw_buffer`, restricted by the scissor box and the color mask.
+fn clear_color_buffer(context: &mut GlContext, framebuffer: Framebuffer, draw_buffer: usize, value: ColorData) {
+    let color_mask = context.color_masks[draw_buffer];
+    let framebuffer_srgb = context.is_flag_enabled(Capability::FramebufferSrgb);
+    match framebuffer {
+        Framebuffer::Default => {
+            let framebuffer = &mut context.default_framebuffer;
+            let rect = context.scissor.clip(framebuffer.width, framebuffer.height);
+            // The default framebuffer only has a single draw buffer
+            if draw_buffer == 0 && let Some(color_buffer) = framebuffer.draw_color_buffer() {
+                color_buffer.fill_rect_masked(rect, color_buffer.srgb_encoded(value, framebuffer_srgb), color_mask);
+            }
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            let Some(active_framebuffer) = context.framebuffer_objects.get_mut(&fbo_id) else {
+                return;
+            };
+            let (width, height) = active_framebuffer.size();
+            let rect = context.scissor.clip(width, height);
+            if let Some(buffer) = active_framebuffer.draw_buffers[draw_buffer]
+                && let Some(attachment) = active_framebuffer.color_attachment(buffer)
+            {
+                attachment.for_each_image(|image| {
+                    let (width, height) = image.size();
+                    image.fill_color_rect_masked(rect.clamped(width, height), value, color_mask, framebuffer_srgb);
+                });
+            }
+        }
     }
-    if n == 0 {
-        return;
+}
+
+// Clears the depth and/or stencil buffer, restricted by the scissor box and the depth and front stencil write masks.
+fn clear_depth_stencil(context: &mut GlContext, framebuffer: Framebuffer, depth: Option<f32>, stencil: Option<u8>) {
+    let depth = depth.filter(|_| context.depth_state.write_mask);
+    let stencil_mask = context.stencil_state.front_write_mask as u8;
+    match framebuffer {
+        Framebuffer::Default => {
+            let framebuffer = &mut context.default_framebuffer;
+            let rect = context.scissor.clip(framebuffer.width, framebuffer.height);
+            if let Some(depth) = depth
+                && let Some(format) = framebuffer.config.depth_format
+                && let Some(depth_buffer) = &mut framebuffer.depth_buffer
+            {
+                depth_buffer.fill_rect(rect, quantize_depth(depth, format.depth_bits()));
+            }
+            if let Some(stencil) = stencil && let Some(stencil_buffer) = &mut framebuffer.stencil_buffer {
+                stencil_buffer.fill_rect_masked(rect, stencil, stencil_mask);
+            }
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            let Some(active_framebuffer) = context.framebuffer_objects.get_mut(&fbo_id) else {
+                return;
+            };
+            let (width, height) = active_framebuffer.size();
+            let rect = context.scissor.clip(width, height);
+            // A depth stencil image attached to both points is filled through each of them, touching only its own component
+            if let Some(depth) = depth
+                && let Some(attachment) = &active_framebuffer.depth_attachment
+                && let Some(info) = attachment.image_info()
+            {
+                let depth = quantize_depth(depth, info.format.depth_bits());
+                attachment.for_each_image(|image| {
+                    let (width, height) = image.size();
+                    image.fill_depth_rect(rect.clamped(width, height), depth);
+                });
+            }
+            if let Some(stencil) = stencil && let Some(attachment) = &active_framebuffer.stencil_attachment {
+                attachment.for_each_image(|image| {
+                    let (width, height) = image.size();
+                    image.fill_stencil_rect_masked(rect.clamped(width, height), stencil, stencil_mask);
+                });
+            }
+        }
+    }
+}
+
+fn framebuffer_status(context: &GlContext, framebuffer: Framebuffer) -> FramebufferStatus {
+    match framebuffer {
+        Framebuffer::Default => FramebufferStatus::Complete,
+        Framebuffer::UserDefined(fbo_id) => context
+            .framebuffer_objects
+            .get(&fbo_id)
+            .map_or(FramebufferStatus::IncompleteMissingAttachment, FBO::status),
     }
-    if bufs.is_null() {
-        return; // TODO: GL_ERROR GL_INVALID_VALUE
+}
+
+// Records INVALID_FRAMEBUFFER_OPERATION unless `framebuffer` is complete. Rendering to it is skipped when it isn't.
+pub(crate) fn check_framebuffer_complete(context: &mut GlContext, framebuffer: Framebuffer) -> bool {
+    let complete = framebuffer_status(context, framebuffer) == FramebufferStatus::Complete;
+    if !complete {
+        context.set_error(ErrorCode::InvalidFramebufferOperation);
     }
-    let bufs_slice = unsafe { slice::from_raw_parts(bufs, n as usize) };
+    complete
+}
+
+// Every buffer selected by `mask` is cleared, restricted by the scissor box and the write masks.
+#[unsafe(no_mangle)]
+pub extern "C" fn glClear(mask: types::GlBitfield) {
     with_current_context(|context| {
-        match &context.framebuffer_state.write_framebuffer {
-            Framebuffer::Default => {
-                if n > 1 {
-                    panic!("Only one draw buffer can be bound to the default Framebuffer"); // TODO: GL_ERROR
-                }
-                for &buf in bufs_slice {
-                    match DrawBufferSys::from_u32(buf) {
-                        Some(DrawBufferSys::Back) | Some(DrawBufferSys::BackLeft) => {
-                            context.default_framebuffer.draw_buffer = DrawBufferSys::BackLeft;
-                        }
-                        Some(DrawBufferSys::Front) | Some(DrawBufferSys::FrontLeft) => {
-                            context.default_framebuffer.draw_buffer = DrawBufferSys::FrontLeft;
-                        }
-                        Some(DrawBufferSys::NONE) => {
-                            context.default_framebuffer.draw_buffer = DrawBufferSys::NONE;
-                        }
-                        _ => panic!("Unknown Enum"), // TODO: GL_ERROR
-                    }
-                }
-            }
-            Framebuffer::UserDefined(fbo_id) => {
-                if n as usize > GL_MAX_COLOR_ATTACHMENTS {
-                    return; // TODO: GL_ERROR GL_INVALID_VALUE
-                }
-                let current_fbo = context.framebuffer_objects.get_mut(fbo_id).unwrap();
-                current_fbo.draw_buffers.fill(None);
-                for &buf in bufs_slice.iter() {
-                    match DrawBufferFBO::from_u32(buf) {
-                        Some(DrawBufferFBO::None) => {}
-                        Some(indexed_attachment) => {
-                            let index = indexed_attachment.get_attachment_index();
-                            current_fbo.draw_buffers[index] = Some(indexed_attachment);
-                        }
-                        _ => todo!(),
-                    }
-                }
+        let known_bits =
+            ClearBufferMask::COLOR as u32 | ClearBufferMask::DEPTH as u32 | ClearBufferMask::STENCIL as u32;
+        if mask & !known_bits != 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let clear_state = context.clear_state;
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        if !check_framebuffer_complete(context, framebuffer) {
+            return;
+        }
+        if mask & ClearBufferMask::COLOR as u32 != 0 {
+            let color = clear_state.color_clear_value;
+            let value = ColorData::Float([color.red, color.green, color.blue, color.alpha]);
+            for draw_buffer in 0..GL_MAX_DRAW_BUFFERS {
+                clear_color_buffer(context, framebuffer, draw_buffer, value);
             }
         }
+        let depth = (mask & ClearBufferMask::DEPTH as u32 != 0).then_some(clear_state.depth_clear_value);
+        let stencil = (mask & ClearBufferMask::STENCIL as u32 != 0).then_some(clear_state.stenctil_clear_value);
+        clear_depth_stencil(context, framebuffer, depth, stencil);
     });
 }
 
-#[unsafe(no_mangle)]
-pub extern "C" fn glNamedFramebufferDrawBuffers(framebuffer: u32, n: i32, bufs: *const u32) {
-    if n < 0 {
-        return; // TODO: GL_ERROR GL_INVALID_VALUE
+// A single glClearBuffer* request after its value has been read
+enum ClearBufferValue {
+    Color(ColorData),
+    Depth(f32),
+    Stencil(i32),
+    DepthStencil(f32, i32),
+}
+
+fn clear_buffer(context: &mut GlContext, framebuffer: Framebuffer, drawbuffer: i32, value: ClearBufferValue) {
+    let max_drawbuffer = match value {
+        ClearBufferValue::Color(_) => GL_MAX_DRAW_BUFFERS as i32,
+        _ => 1,
+    };
+    if !(0..max_drawbuffer).contains(&drawbuffer) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
     }
-    if n == 0 {
+    if !check_framebuffer_complete(context, framebuffer) {
         return;
     }
-    if bufs.is_null() {
-        return; // TODO: GL_ERROR GL_INVALID_VALUE
+    match value {
+        ClearBufferValue::Color(color) => clear_color_buffer(context, framebuffer, drawbuffer as usize, color),
+        ClearBufferValue::Depth(depth) => {
+            clear_depth_stencil(context, framebuffer, Some(depth.clamp(0.0, 1.0)), None);
+        }
+        // Masked to the number of stencil bits
+        ClearBufferValue::Stencil(stencil) => clear_depth_stencil(context, framebuffer, None, Some(stencil as u8)),
+        ClearBufferValue::DepthStencil(depth, stencil) => {
+            clear_depth_stencil(context, framebuffer, Some(depth.clamp(0.0, 1.0)), Some(stencil as u8));
+        }
+    }
+}
+
+// Reads the value of a glClearBuffer*v call. Colors take four components, everything else one.
+fn read_clear_value<T: Copy + Default>(buffer: ClearBuffer, value: *const T) -> [T; 4] {
+    let mut data = [T::default(); 4];
+    if value.is_null() {
+        return data;
     }
-    if n as usize > GL_MAX_COLOR_ATTACHMENTS {
-        return; // TODO: GL_ERROR GL_INVALID_VALUE
+    let count = if buffer == ClearBuffer::Color { 4 } else { 1 };
+    data[..count].copy_from_slice(unsafe { slice::from_raw_parts(value, count) });
+    data
+}
+
+fn clear_buffer_fv(context: &mut GlContext, framebuffer: Framebuffer, buffer: u32, drawbuffer: i32, value: *const f32) {
+    let value = match ClearBuffer::from_u32(buffer) {
+        Some(buffer @ ClearBuffer::Color) => ClearBufferValue::Color(ColorData::Float(read_clear_value(buffer, value))),
+        Some(buffer @ ClearBuffer::Depth) => ClearBufferValue::Depth(read_clear_value(buffer, value)[0]),
+        _ => {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+    };
+    clear_buffer(context, framebuffer, drawbuffer, value);
+}
+
+fn clear_buffer_iv(context: &mut GlContext, framebuffer: Framebuffer, buffer: u32, drawbuffer: i32, value: *const i32) {
+    let value = match ClearBuffer::from_u32(buffer) {
+        Some(buffer @ ClearBuffer::Color) => ClearBufferValue::Color(ColorData::Int(read_clear_value(buffer, value))),
+        Some(buffer @ ClearBuffer::Stencil) => ClearBufferValue::Stencil(read_clear_value(buffer, value)[0]),
+        _ => {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+    };
+    clear_buffer(context, framebuffer, drawbuffer, value);
+}
+
+fn clear_buffer_uiv(context: &mut GlContext, framebuffer: Framebuffer, buffer: u32, drawbuffer: i32, value: *const u32) {
+    let value = match ClearBuffer::from_u32(buffer) {
+        Some(buffer @ ClearBuffer::Color) => {
+            ClearBufferValue::Color(ColorData::UnsignedInt(read_clear_value(buffer, value)))
+        }
+        _ => {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+    };
+    clear_buffer(context, framebuffer, drawbuffer, value);
+}
+
+fn clear_buffer_fi(context: &mut GlContext, framebuffer: Framebuffer, buffer: u32, drawbuffer: i32, depth: f32, stencil: i32) {
+    if ClearBuffer::from_u32(buffer) != Some(ClearBuffer::DepthStencil) {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
     }
-    let bufs_slice = unsafe { slice::from_raw_parts(bufs, n as usize) };
+    clear_buffer(context, framebuffer, drawbuffer, ClearBufferValue::DepthStencil(depth, stencil));
+}
 
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearBufferfv(buffer: u32, drawbuffer: i32, value: *const f32) {
     with_current_context(|context| {
-        if let Some(fbo) = context.framebuffer_objects.get_mut(&framebuffer) {
-            fbo.draw_buffers.fill(None);
-            for &buf in bufs_slice.iter() {
-                match DrawBufferFBO::from_u32(buf) {
-                    Some(DrawBufferFBO::None) => {}
-                    Some(indexed_attachment) => {
-                        let index = indexed_attachment.get_attachment_index();
-                        fbo.draw_buffers[index] = Some(indexed_attachment);
-                    }
-                    _ => todo!(),
-                }
-            }
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        clear_buffer_fv(context, framebuffer, buffer, drawbuffer, value);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearBufferiv(buffer: u32, drawbuffer: i32, value: *const i32) {
+    with_current_context(|context| {
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        clear_buffer_iv(context, framebuffer, buffer, drawbuffer, value);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearBufferuiv(buffer: u32, drawbuffer: i32, value: *const u32) {
+    with_current_context(|context| {
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        clear_buffer_uiv(context, framebuffer, buffer, drawbuffer, value);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearBufferfi(buffer: u32, drawbuffer: i32, depth: f32, stencil: i32) {
+    with_current_context(|context| {
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        clear_buffer_fi(context, framebuffer, buffer, drawbuffer, depth, stencil);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearNamedFramebufferfv(framebuffer: u32, buffer: u32, drawbuffer: i32, value: *const f32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            clear_buffer_fv(context, framebuffer, buffer, drawbuffer, value);
         }
     });
 }
 
 #[unsafe(no_mangle)]
-pub extern "C" fn glDrawBuffer(buf: u32) {
+pub extern "C" fn glClearNamedFramebufferiv(framebuffer: u32, buffer: u32, drawbuffer: i32, value: *const i32) {
     with_current_context(|context| {
-        match &context.framebuffer_state.write_framebuffer {
-            Framebuffer::Default => {
-                match DrawBufferSys::from_u32(buf) {
-                    Some(DrawBufferSys::Back) | Some(DrawBufferSys::BackLeft) => {
-                        context.default_framebuffer.draw_buffer = DrawBufferSys::BackLeft;
-                    }
-                    Some(DrawBufferSys::Front) | Some(DrawBufferSys::FrontLeft) => {
-                        context.default_framebuffer.draw_buffer = DrawBufferSys::FrontLeft;
-                    }
-                    Some(DrawBufferSys::NONE) => {
-                        context.default_framebuffer.draw_buffer = DrawBufferSys::NONE;
-                    }
-                    _ => panic!("Unknown Enum"), // TODO: GL_ERROR
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            clear_buffer_iv(context, framebuffer, buffer, drawbuffer, value);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearNamedFramebufferuiv(framebuffer: u32, buffer: u32, drawbuffer: i32, value: *const u32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            clear_buffer_uiv(context, framebuffer, buffer, drawbuffer, value);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glClearNamedFramebufferfi(framebuffer: u32, buffer: u32, drawbuffer: i32, depth: f32, stencil: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            clear_buffer_fi(context, framebuffer, buffer, drawbuffer, depth, stencil);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetError() -> u32 {
+    with_current_context(|context| mem::replace(&mut context.error, ErrorCode::NoError) as u32)
+        .unwrap_or(ErrorCode::NoError as u32)
+}
+
+// Validates a default framebuffer buffer selection. The selection is stored as passed so queries return it, and only
+// resolved to a buffer when one is accessed.
+fn is_default_color_buffer(context: &mut GlContext, buf: DrawBufferSys) -> bool {
+    match buf {
+        DrawBufferSys::Back | DrawBufferSys::BackLeft if !context.default_framebuffer.config.double_buffered => {
+            context.set_error(ErrorCode::InvalidOperation);
+            false
+        }
+        DrawBufferSys::Back | DrawBufferSys::BackLeft | DrawBufferSys::Front | DrawBufferSys::FrontLeft | DrawBufferSys::NONE => true,
+        // Stereo buffers are not implemented so they never exist.
+        DrawBufferSys::FrontRight | DrawBufferSys::BackRight => {
+            context.set_error(ErrorCode::InvalidOperation);
+            false
+        }
+    }
+}
+
+fn set_default_draw_buffer(context: &mut GlContext, buf: DrawBufferSys) {
+    if is_default_color_buffer(context, buf) {
+        context.default_framebuffer.draw_buffer = buf;
+    }
+}
+
+// Resolves a framebuffer name as used by the DSA entry points. Zero names the default framebuffer.
+fn named_framebuffer(context: &mut GlContext, framebuffer: u32) -> Option<Framebuffer> {
+    if framebuffer == 0 {
+        Some(Framebuffer::Default)
+    } else if context.framebuffer_objects.contains_key(&framebuffer) {
+        Some(Framebuffer::UserDefined(framebuffer))
+    } else {
+        context.set_error(ErrorCode::InvalidOperation);
+        None
+    }
+}
+
+fn set_draw_buffers(context: &mut GlContext, framebuffer: Framebuffer, bufs: &[u32]) {
+    match framebuffer {
+        Framebuffer::Default => {
+            if bufs.len() != 1 {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
+            }
+            match DrawBufferSys::from_u32(bufs[0]) {
+                // Selects more than one buffer, which is only allowed for glDrawBuffer
+                Some(DrawBufferSys::Front) => {
+                    context.set_error(ErrorCode::InvalidEnum);
+                }
+                Some(buf) => set_default_draw_buffer(context, buf),
+                None if DrawBufferFBO::from_u32(bufs[0]).is_some() => {
+                    context.set_error(ErrorCode::InvalidOperation);
                 }
+                None => context.set_error(ErrorCode::InvalidEnum),
             }
-            Framebuffer::UserDefined(fbo_id) => {
-                
-                let current_fbo = context.framebuffer_objects.get_mut(fbo_id).unwrap();
-                current_fbo.draw_buffers.fill(None);
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            // Indexed by draw buffer, not by attachment
+            let mut draw_buffers = [None; GL_MAX_COLOR_ATTACHMENTS];
+            for (i, &buf) in bufs.iter().enumerate() {
                 match DrawBufferFBO::from_u32(buf) {
                     Some(DrawBufferFBO::None) => {}
                     Some(indexed_attachment) => {
-                        let index = indexed_attachment.get_attachment_index();
-                        current_fbo.draw_buffers[index] = Some(indexed_attachment);
+                        if draw_buffers.contains(&Some(indexed_attachment)) {
+                            context.set_error(ErrorCode::InvalidOperation);
+                            return;
+                        }
+                        draw_buffers[i] = Some(indexed_attachment);
+                    }
+                    None if DrawBufferSys::from_u32(buf).is_some() => {
+                        context.set_error(ErrorCode::InvalidOperation);
+                        return;
                     }
-                    _ => todo!(),
+                    None => {
+                        context.set_error(ErrorCode::InvalidEnum);
+                        return;
+                    }
+                }
+            }
+            if let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id) {
+                fbo.draw_buffers = draw_buffers;
+            }
+        }
+    }
+}
+
+fn set_draw_buffer(context: &mut GlContext, framebuffer: Framebuffer, buf: u32) {
+    match framebuffer {
+        Framebuffer::Default => match DrawBufferSys::from_u32(buf) {
+            Some(buf) => set_default_draw_buffer(context, buf),
+            None if DrawBufferFBO::from_u32(buf).is_some() => {
+                context.set_error(ErrorCode::InvalidOperation);
+            }
+            None => context.set_error(ErrorCode::InvalidEnum),
+        },
+        Framebuffer::UserDefined(fbo_id) => {
+            let attachment = match DrawBufferFBO::from_u32(buf) {
+                Some(DrawBufferFBO::None) => None,
+                Some(indexed_attachment) => Some(indexed_attachment),
+                None if DrawBufferSys::from_u32(buf).is_some() => {
+                    context.set_error(ErrorCode::InvalidOperation);
+                    return;
+                }
+                None => {
+                    context.set_error(ErrorCode::InvalidEnum);
+                    return;
                 }
+            };
+            if let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id) {
+                fbo.draw_buffers.fill(None);
+                fbo.draw_buffers[0] = attachment;
             }
         }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDrawBuffers(n: i32, bufs: *const u32) {
+    with_current_context(|context| {
+        if n < 0 || n as usize > GL_MAX_COLOR_ATTACHMENTS || (n > 0 && bufs.is_null()) {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let bufs_slice = if n == 0 { &[] } else { unsafe { slice::from_raw_parts(bufs, n as usize) } };
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        set_draw_buffers(context, framebuffer, bufs_slice);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferDrawBuffers(framebuffer: u32, n: i32, bufs: *const u32) {
+    with_current_context(|context| {
+        let Some(framebuffer) = named_framebuffer(context, framebuffer) else {
+            return;
+        };
+        if n < 0 || n as usize > GL_MAX_COLOR_ATTACHMENTS || (n > 0 && bufs.is_null()) {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let bufs_slice = if n == 0 { &[] } else { unsafe { slice::from_raw_parts(bufs, n as usize) } };
+        set_draw_buffers(context, framebuffer, bufs_slice);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDrawBuffer(buf: u32) {
+    with_current_context(|context| {
+        let framebuffer = context.framebuffer_state.write_framebuffer;
+        set_draw_buffer(context, framebuffer, buf);
     });
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn glNamedFramebufferDrawBuffer(framebuffer: u32, buf: u32) {
     with_current_context(|context| {
-        if let Some(fbo) = context.framebuffer_objects.get_mut(&framebuffer) {
-            fbo.draw_buffers.fill(None);
-            match DrawBufferFBO::from_u32(buf) {
-                Some(DrawBufferFBO::None) => {}
-                Some(indexed_attachment) => {
-                    let index = indexed_attachment.get_attachment_index();
-                    fbo.draw_buffers[index] = Some(indexed_attachment);
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            set_draw_buffer(context, framebuffer, buf);
+        }
+    });
+}
+
+fn set_read_buffer(context: &mut GlContext, framebuffer: Framebuffer, src: u32) {
+    match framebuffer {
+        Framebuffer::Default => match DrawBufferSys::from_u32(src) {
+            Some(src) => {
+                if is_default_color_buffer(context, src) {
+                    context.default_framebuffer.read_buffer = src;
                 }
-                _ => todo!(),
+            }
+            None if DrawBufferFBO::from_u32(src).is_some() => context.set_error(ErrorCode::InvalidOperation),
+            None => context.set_error(ErrorCode::InvalidEnum),
+        },
+        Framebuffer::UserDefined(fbo_id) => {
+            let attachment = match DrawBufferFBO::from_u32(src) {
+                Some(DrawBufferFBO::None) => None,
+                Some(attachment) => Some(attachment),
+                None if DrawBufferSys::from_u32(src).is_some() => {
+                    context.set_error(ErrorCode::InvalidOperation);
+                    return;
+                }
+                None => {
+                    context.set_error(ErrorCode::InvalidEnum);
+                    return;
+                }
+            };
+            if let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id) {
+                fbo.read_buffer = attachment;
             }
         }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glReadBuffer(src: u32) {
+    with_current_context(|context| {
+        let framebuffer = context.framebuffer_state.read_framebuffer;
+        set_read_buffer(context, framebuffer, src);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferReadBuffer(framebuffer: u32, src: u32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            set_read_buffer(context, framebuffer, src);
+        }
     });
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn glGenFramebuffers(n: GlSizei, ids: *mut u32) {
-    let framebuffer_ids = unsafe { slice::from_raw_parts_mut(ids, n as usize) };
     with_current_context(|context| {
-        for i in 0..n as usize {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || ids.is_null() {
+            return;
+        }
+        let framebuffer_ids = unsafe { slice::from_raw_parts_mut(ids, n as usize) };
+        for id in framebuffer_ids.iter_mut() {
             let key = context.next_fb_id;
-            framebuffer_ids[i] = key;
-            let framebuffer = FBO::new(
-                context.default_framebuffer.width,
-                context.default_framebuffer.height,
-            );
-            context.framebuffer_objects.insert(key, framebuffer);
+            *id = key;
+            context.framebuffer_objects.insert(key, FBO::new());
             context.next_fb_id += 1;
         }
     });
@@ -232,45 +645,724 @@ pub extern "C" fn glGenFramebuffers(n: GlSizei, ids: *mut u32) {
 
 #[unsafe(no_mangle)]
 pub extern "C" fn glDeleteFramebuffers(n: GlSizei, framebuffers: *const u32) {
-    let framebuffer_ids = unsafe { slice::from_raw_parts(framebuffers, n as usize) };
     with_current_context(|context| {
-        for i in 0..n as usize {
-            let key = &framebuffer_ids[i];
-            context.framebuffer_objects.remove(key);
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || framebuffers.is_null() {
+            return;
+        }
+        let framebuffer_ids = unsafe { slice::from_raw_parts(framebuffers, n as usize) };
+        for &key in framebuffer_ids {
+            // Zero and unused names are silently ignored.
+            if context.framebuffer_objects.remove(&key).is_none() {
+                continue;
+            }
+            // Deleting a bound framebuffer reverts the binding to the default framebuffer.
+            if context.framebuffer_state.write_framebuffer == Framebuffer::UserDefined(key) {
+                context.framebuffer_state.write_framebuffer = Framebuffer::Default;
+            }
+            if context.framebuffer_state.read_framebuffer == Framebuffer::UserDefined(key) {
+                context.framebuffer_state.read_framebuffer = Framebuffer::Default;
+            }
         }
     });
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn glBindFramebuffer(target: u32, framebuffer: u32) {
-    if target == 0 {
-        with_current_context(|context| match FramebufferTypes::from_u32(framebuffer) {
-            Some(FramebufferTypes::DrawFramebuffer) => {
-                context.framebuffer_state.write_framebuffer = Framebuffer::Default
+    with_current_context(|context| {
+        let Some(target) = FramebufferTypes::from_u32(target) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let framebuffer = if framebuffer == 0 {
+            Framebuffer::Default
+        } else if context.framebuffer_objects.contains_key(&framebuffer) {
+            Framebuffer::UserDefined(framebuffer)
+        } else {
+            // Names must come from glGenFramebuffers
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        };
+        match target {
+            FramebufferTypes::DrawFramebuffer => {
+                context.framebuffer_state.write_framebuffer = framebuffer
             }
-            Some(FramebufferTypes::ReadFramebuffer) => {
-                context.framebuffer_state.read_framebuffer = Framebuffer::Default
+            FramebufferTypes::ReadFramebuffer => {
+                context.framebuffer_state.read_framebuffer = framebuffer
             }
-            Some(FramebufferTypes::Framebuffer) => {
-                context.framebuffer_state.read_framebuffer = Framebuffer::Default;
-                context.framebuffer_state.write_framebuffer = Framebuffer::Default
+            FramebufferTypes::Framebuffer => {
+                context.framebuffer_state.read_framebuffer = framebuffer;
+                context.framebuffer_state.write_framebuffer = framebuffer
             }
-            _ => panic!(),
-        })
+        }
+    });
+}
+
+// Framebuffer bound to `target`, recording INVALID_ENUM for an unknown target
+fn target_framebuffer(context: &mut GlContext, target: u32) -> Option<Framebuffer> {
+    match FramebufferTypes::from_u32(target) {
+        Some(FramebufferTypes::ReadFramebuffer) => Some(context.framebuffer_state.read_framebuffer),
+        Some(FramebufferTypes::DrawFramebuffer | FramebufferTypes::Framebuffer) => {
+            Some(context.framebuffer_state.write_framebuffer)
+        }
+        None => {
+            context.set_error(ErrorCode::InvalidEnum);
+            None
+        }
+    }
+}
+
+// Resolves `attachment` of `framebuffer` into the FBO name and attachment point to attach to
+fn attachment_point(context: &mut GlContext, framebuffer: Framebuffer, attachment: u32) -> Option<(u32, AttachmentPoint)> {
+    let Some(point) = AttachmentPoint::from_u32(attachment) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    };
+    // The default framebuffer has no attachment points
+    let Framebuffer::UserDefined(fbo_id) = framebuffer else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    };
+    Some((fbo_id, point))
+}
+
+fn attach(context: &mut GlContext, fbo_id: u32, point: AttachmentPoint, attachment: Option<Attachment>) {
+    if let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id) {
+        fbo.attach(point, attachment);
+    }
+}
+
+// Attaches `renderbuffer` to `attachment` of `framebuffer`. Renderbuffer zero detaches the current image.
+fn framebuffer_renderbuffer(context: &mut GlContext, framebuffer: Framebuffer, attachment: u32, renderbuffertarget: u32, renderbuffer: u32) {
+    if renderbuffertarget != GL_RENDERBUFFER {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    }
+    let Some((fbo_id, point)) = attachment_point(context, framebuffer, attachment) else {
+        return;
+    };
+    let attached = if renderbuffer == 0 {
+        None
     } else {
-        with_current_context(|context| match FramebufferTypes::from_u32(framebuffer) {
-            Some(FramebufferTypes::DrawFramebuffer) => {
-                context.framebuffer_state.write_framebuffer = Framebuffer::UserDefined(target)
+        let shared = context.shared.lock().unwrap();
+        let Some(object) = shared.renderbuffers.objects.get(&renderbuffer).cloned() else {
+            drop(shared);
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        };
+        Some(Attachment::Renderbuffer { name: renderbuffer, renderbuffer: object })
+    };
+    attach(context, fbo_id, point, attached);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glFramebufferRenderbuffer(target: u32, attachment: u32, renderbuffertarget: u32, renderbuffer: u32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = target_framebuffer(context, target) {
+            framebuffer_renderbuffer(context, framebuffer, attachment, renderbuffertarget, renderbuffer);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferRenderbuffer(framebuffer: u32, attachment: u32, renderbuffertarget: u32, renderbuffer: u32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            framebuffer_renderbuffer(context, framebuffer, attachment, renderbuffertarget, renderbuffer);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glCheckFramebufferStatus(target: u32) -> u32 {
+    with_current_context(|context| match target_framebuffer(context, target) {
+        Some(framebuffer) => framebuffer_status(context, framebuffer) as u32,
+        None => 0,
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glCheckNamedFramebufferStatus(framebuffer: u32, target: u32) -> u32 {
+    with_current_context(|context| {
+        // The target is only validated, the status doesn't depend on it
+        FramebufferTypes::from_u32(target).or_else(|| {
+            context.set_error(ErrorCode::InvalidEnum);
+            None
+        })?;
+        let framebuffer = named_framebuffer(context, framebuffer)?;
+        Some(framebuffer_status(context, framebuffer) as u32)
+    })
+    .flatten()
+    .unwrap_or(0)
+}
+
+// Framebuffer object behind `framebuffer`. The default framebuffer has no framebuffer parameters.
+fn framebuffer_object(context: &mut GlContext, framebuffer: Framebuffer) -> Option<&mut FBO> {
+    let Framebuffer::UserDefined(fbo_id) = framebuffer else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    };
+    context.framebuffer_objects.get_mut(&fbo_id)
+}
+
+fn framebuffer_parameter(context: &mut GlContext, framebuffer: Framebuffer, pname: u32, param: i32) {
+    let Some(pname) = FramebufferParameter::from_u32(pname) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    let max = match pname {
+        FramebufferParameter::Width => GL_MAX_FRAMEBUFFER_WIDTH,
+        FramebufferParameter::Height => GL_MAX_FRAMEBUFFER_HEIGHT,
+        FramebufferParameter::Layers => GL_MAX_FRAMEBUFFER_LAYERS,
+        FramebufferParameter::Samples => GL_MAX_FRAMEBUFFER_SAMPLES,
+        FramebufferParameter::FixedSampleLocations => i32::MAX,
+    };
+    if !(0..=max).contains(&param) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let Some(fbo) = framebuffer_object(context, framebuffer) else {
+        return;
+    };
+    match pname {
+        FramebufferParameter::Width => fbo.default_width = param as usize,
+        FramebufferParameter::Height => fbo.default_height = param as usize,
+        FramebufferParameter::Layers => fbo.default_layers = param as usize,
+        FramebufferParameter::Samples => fbo.default_samples = param as u32,
+        FramebufferParameter::FixedSampleLocations => fbo.default_fixed_sample_locations = param != 0,
+    }
+}
+
+fn get_framebuffer_parameter(context: &mut GlContext, framebuffer: Framebuffer, pname: u32, params: *mut i32) {
+    let Some(pname) = FramebufferParameter::from_u32(pname) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    let Some(fbo) = framebuffer_object(context, framebuffer) else {
+        return;
+    };
+    let value = match pname {
+        FramebufferParameter::Width => fbo.default_width as i32,
+        FramebufferParameter::Height => fbo.default_height as i32,
+        FramebufferParameter::Layers => fbo.default_layers as i32,
+        FramebufferParameter::Samples => fbo.default_samples as i32,
+        FramebufferParameter::FixedSampleLocations => fbo.default_fixed_sample_locations as i32,
+    };
+    if !params.is_null() {
+        unsafe { *params = value };
+    }
+}
+
+// Sets the size and sample layout used by a framebuffer while nothing is attached to it
+#[unsafe(no_mangle)]
+pub extern "C" fn glFramebufferParameteri(target: u32, pname: u32, param: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = target_framebuffer(context, target) {
+            framebuffer_parameter(context, framebuffer, pname, param);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferParameteri(framebuffer: u32, pname: u32, param: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            framebuffer_parameter(context, framebuffer, pname, param);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetFramebufferParameteriv(target: u32, pname: u32, params: *mut i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = target_framebuffer(context, target) {
+            get_framebuffer_parameter(context, framebuffer, pname, params);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetNamedFramebufferParameteriv(framebuffer: u32, pname: u32, param: *mut i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            get_framebuffer_parameter(context, framebuffer, pname, param);
+        }
+    });
+}
+
+// How a texture image is selected by the glFramebufferTexture* variants
+#[derive(Clone, Copy)]
+enum TextureImage {
+    // glFramebufferTexture, layered for layered textures
+    Whole,
+    // glFramebufferTexture2D with the texture target and cube map face
+    Target(TextureTarget, usize),
+    // glFramebufferTextureLayer
+    Layer(i32),
+}
+
+// Texture target and cube map face selected by the textarget of glFramebufferTexture2D
+fn texture_2d_target(textarget: u32) -> Option<(TextureTarget, usize)> {
+    if (GL_TEXTURE_CUBE_MAP_POSITIVE_X..=GL_TEXTURE_CUBE_MAP_NEGATIVE_Z).contains(&textarget) {
+        return Some((TextureTarget::TextureCubeMap, (textarget - GL_TEXTURE_CUBE_MAP_POSITIVE_X) as usize));
+    }
+    match TextureTarget::from_u32(textarget)? {
+        target @ (TextureTarget::Texture2D | TextureTarget::TextureRectangle | TextureTarget::Texture2DMultisample) => {
+            Some((target, 0))
+        }
+        _ => None,
+    }
+}
+
+// Attaches `level` of `texture` to `attachment` of `framebuffer`. Texture zero detaches the current image.
+fn framebuffer_texture(context: &mut GlContext, framebuffer: Framebuffer, attachment: u32, texture: u32, level: i32, image: TextureImage) {
+    let Some((fbo_id, point)) = attachment_point(context, framebuffer, attachment) else {
+        return;
+    };
+    if texture == 0 {
+        attach(context, fbo_id, point, None);
+        return;
+    }
+    let object = context.shared.lock().unwrap().textures.objects.get(&texture).cloned();
+    // The texture has to exist, which it only does once it has been bound
+    let Some((object, target)) = object.and_then(|object| {
+        let target = object.lock().unwrap().target?;
+        Some((object, target))
+    }) else {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    };
+    let max_level = match target {
+        _ if target.has_single_level() => 0,
+        TextureTarget::Texture3D => GL_MAX_3D_TEXTURE_SIZE.ilog2() as i32,
+        _ => GL_MAX_TEXTURE_SIZE.ilog2() as i32,
+    };
+    if !(0..=max_level).contains(&level) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let layer = match image {
+        TextureImage::Whole => (!target.is_layered()).then_some(0),
+        TextureImage::Target(textarget, face) => {
+            if textarget != target {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
             }
-            Some(FramebufferTypes::ReadFramebuffer) => {
-                context.framebuffer_state.read_framebuffer = Framebuffer::UserDefined(target)
+            Some(face)
+        }
+        TextureImage::Layer(layer) => {
+            if !target.is_layered() {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
             }
-            Some(FramebufferTypes::Framebuffer) => {
-                context.framebuffer_state.read_framebuffer = Framebuffer::UserDefined(target);
-                context.framebuffer_state.write_framebuffer = Framebuffer::UserDefined(target)
+            let max_layers = match target {
+                TextureTarget::Texture3D => GL_MAX_3D_TEXTURE_SIZE,
+                TextureTarget::TextureCubeMap => 6,
+                _ => GL_MAX_ARRAY_TEXTURE_LAYERS,
+            };
+            if !(0..max_layers).contains(&layer) {
+                context.set_error(ErrorCode::InvalidValue);
+                return;
             }
-            None => panic!(),
-        });
+            Some(layer as usize)
+        }
+    };
+    let attached = Attachment::Texture { name: texture, texture: object, level: level as usize, layer };
+    attach(context, fbo_id, point, Some(attached));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glFramebufferTexture(target: u32, attachment: u32, texture: u32, level: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = target_framebuffer(context, target) {
+            framebuffer_texture(context, framebuffer, attachment, texture, level, TextureImage::Whole);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glFramebufferTexture2D(target: u32, attachment: u32, textarget: u32, texture: u32, level: i32) {
+    with_current_context(|context| {
+        let Some(framebuffer) = target_framebuffer(context, target) else {
+            return;
+        };
+        // The texture target is checked even when detaching
+        let Some((textarget, face)) = texture_2d_target(textarget) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        framebuffer_texture(context, framebuffer, attachment, texture, level, TextureImage::Target(textarget, face));
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glFramebufferTextureLayer(target: u32, attachment: u32, texture: u32, level: i32, layer: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = target_framebuffer(context, target) {
+            framebuffer_texture(context, framebuffer, attachment, texture, level, TextureImage::Layer(layer));
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferTexture(framebuffer: u32, attachment: u32, texture: u32, level: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            framebuffer_texture(context, framebuffer, attachment, texture, level, TextureImage::Whole);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glNamedFramebufferTextureLayer(framebuffer: u32, attachment: u32, texture: u32, level: i32, layer: i32) {
+    with_current_context(|context| {
+        if let Some(framebuffer) = named_framebuffer(context, framebuffer) {
+            framebuffer_texture(context, framebuffer, attachment, texture, level, TextureImage::Layer(layer));
+        }
+    });
+}
+
+// Pixels of a single buffer, bottom row first. Multisampled buffers store the samples of each pixel next to each other.
+pub(crate) struct Plane<'a, T> {
+    pub pixels: &'a mut [T],
+    pub width: usize,
+    pub height: usize,
+    pub samples: u32,
+}
+
+impl<'a, T> Plane<'a, T> {
+    pub(crate) fn new(pixels: &'a mut [T], width: usize, height: usize, samples: u32) -> Self {
+        Self { pixels, width, height, samples }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum ColorSelect {
+    Read,
+    Draw(usize),
+}
+
+// Runs `f` on the color buffer of `framebuffer` selected by `buffer`. Returns None when no buffer is selected.
+// Layered attachments expose their first layer.
+pub(crate) fn with_color_buffer<R>(context: &mut GlContext, framebuffer: Framebuffer, buffer: ColorSelect, f: impl FnOnce(&mut ColorBuffer) -> R) -> Option<R> {
+    match framebuffer {
+        Framebuffer::Default => {
+            let color_buffer = match buffer {
+                ColorSelect::Read => context.default_framebuffer.read_color_buffer()?,
+                ColorSelect::Draw(0) => context.default_framebuffer.draw_color_buffer()?,
+                ColorSelect::Draw(_) => return None,
+            };
+            Some(f(color_buffer))
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            let fbo = context.framebuffer_objects.get_mut(&fbo_id)?;
+            let selected = match buffer {
+                ColorSelect::Read => fbo.read_buffer,
+                ColorSelect::Draw(draw_buffer) => fbo.draw_buffers[draw_buffer],
+            };
+            fbo.color_attachment(selected?)?
+                .with_image(|image| match image {
+                    Image::Color(color_buffer) => Some(f(color_buffer)),
+                    _ => None,
+                })
+                .flatten()
+        }
+    }
+}
+
+// Runs `f` on the depth buffer of `framebuffer` and its number of bits. Returns None when there is none.
+pub(crate) fn with_depth_plane<R>(context: &mut GlContext, framebuffer: Framebuffer, f: impl FnOnce(Plane<f32>, u32) -> R) -> Option<R> {
+    match framebuffer {
+        Framebuffer::Default => {
+            let bits = context.default_framebuffer.config.depth_format?.depth_bits();
+            let depth_buffer = context.default_framebuffer.depth_buffer.as_mut()?;
+            Some(f(Plane::new(&mut depth_buffer.pixels, depth_buffer.width, depth_buffer.height, depth_buffer.samples), bits))
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            let attachment = context.framebuffer_objects.get(&fbo_id)?.depth_attachment.as_ref()?;
+            let bits = attachment.image_info()?.format.depth_bits();
+            attachment
+                .with_image(|image| match image {
+                    Image::Depth(buffer) => Some(f(Plane::new(&mut buffer.pixels, buffer.width, buffer.height, buffer.samples), bits)),
+                    Image::DepthStencil(buffer) => {
+                        Some(f(Plane::new(&mut buffer.depth, buffer.width, buffer.height, buffer.samples), bits))
+                    }
+                    _ => None,
+                })
+                .flatten()
+        }
+    }
+}
+
+// Runs `f` on the stencil buffer of `framebuffer` and its number of bits. Returns None when there is none.
+pub(crate) fn with_stencil_plane<R>(context: &mut GlContext, framebuffer: Framebuffer, f: impl FnOnce(Plane<u8>, u32) -> R) -> Option<R> {
+    match framebuffer {
+        Framebuffer::Default => {
+            let bits = context.default_framebuffer.config.stencil_bits;
+            let stencil_buffer = context.default_framebuffer.stencil_buffer.as_mut()?;
+            Some(f(Plane::new(&mut stencil_buffer.pixels, stencil_buffer.width, stencil_buffer.height, stencil_buffer.samples), bits))
+        }
+        Framebuffer::UserDefined(fbo_id) => {
+            let attachment = context.framebuffer_objects.get(&fbo_id)?.stencil_attachment.as_ref()?;
+            let bits = attachment.image_info()?.format.stencil_bits();
+            attachment
+                .with_image(|image| match image {
+                    Image::Stencil(buffer) => Some(f(Plane::new(&mut buffer.pixels, buffer.width, buffer.height, buffer.samples), bits)),
+                    Image::DepthStencil(buffer) => {
+                        Some(f(Plane::new(&mut buffer.stencil, buffer.width, buffer.height, buffer.samples), bits))
+                    }
+                    _ => None,
+                })
+                .flatten()
+        }
+    }
+}
+
+// Samples of each pixel of `framebuffer`, zero for single sampled framebuffers
+pub(crate) fn framebuffer_samples(context: &GlContext, framebuffer: Framebuffer) -> u32 {
+    match framebuffer {
+        Framebuffer::Default => context.default_framebuffer.config.samples,
+        Framebuffer::UserDefined(fbo_id) => {
+            let Some(fbo) = context.framebuffer_objects.get(&fbo_id) else {
+                return 0;
+            };
+            // A complete framebuffer has the same number of samples in every attachment
+            fbo.attachments().find_map(Attachment::image_info).map_or(fbo.default_samples, |info| info.samples)
+        }
+    }
+}
+
+// Source pixels of a blit. They are copied out before anything is written so a blit within a single image
+// reads the original contents.
+struct BlitSource<T> {
+    // Bounding box of the source rectangle inside the source buffer
+    region: Rect,
+    pixels: Vec<T>,
+}
+
+impl<T: Copy> BlitSource<T> {
+    // Copies the source rectangle out of a `width` x `height` buffer whose pixels are read with `pixel`
+    fn copy((width, height): (usize, usize), [x0, y0, x1, y1]: [i32; 4], pixel: impl Fn(usize) -> T) -> Self {
+        let clamp = |value: i32, size: usize| (value.max(0) as usize).min(size);
+        let region = Rect {
+            x0: clamp(x0.min(x1), width),
+            y0: clamp(y0.min(y1), height),
+            x1: clamp(x0.max(x1), width),
+            y1: clamp(y0.max(y1), height),
+        };
+        let mut pixels = Vec::with_capacity((region.x1 - region.x0) * (region.y1 - region.y0));
+        for row in region.y0..region.y1 {
+            pixels.extend((row * width + region.x0..row * width + region.x1).map(&pixel));
+        }
+        Self { region, pixels }
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let region = self.region;
+        (region.x0 as f64..region.x1 as f64).contains(&x) && (region.y0 as f64..region.y1 as f64).contains(&y)
+    }
+
+    // Pixel at `x`, `y` clamped to the source region, which must not be empty
+    fn get(&self, x: i64, y: i64) -> T {
+        let region = self.region;
+        let x = x.clamp(region.x0 as i64, region.x1 as i64 - 1) as usize - region.x0;
+        let y = y.clamp(region.y0 as i64, region.y1 as i64 - 1) as usize - region.y0;
+        self.pixels[y * (region.x1 - region.x0) + x]
+    }
+
+    // Pixels whose center falls outside of the source rectangle are not written
+    fn nearest(&self, x: f64, y: f64) -> Option<T> {
+        self.contains(x, y).then(|| self.get(x.floor() as i64, y.floor() as i64))
+    }
+}
+
+impl BlitSource<ColorData> {
+    // Integer colors are never filtered linearly
+    fn linear(&self, x: f64, y: f64) -> Option<ColorData> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        // Weights of the four pixel centers around the sample position
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = ((x - x0) as f32, (y - y0) as f32);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let lerp = |a: [f32; 4], b: [f32; 4], t: f32| array::from_fn(|index| a[index] + (b[index] - a[index]) * t);
+        let pixel = |x: i64, y: i64| self.get(x, y).to_floats();
+        let bottom = lerp(pixel(x0, y0), pixel(x0 + 1, y0), fx);
+        let top = lerp(pixel(x0, y0 + 1), pixel(x0 + 1, y0 + 1), fx);
+        Some(ColorData::Float(lerp(bottom, top, fy)))
+    }
+}
+
+// Source and destination rectangles of a blit as passed to glBlitFramebuffer
+#[derive(Clone, Copy)]
+struct BlitRects {
+    src: [i32; 4],
+    dst: [i32; 4],
+}
+
+// Writes every pixel of a `width` x `height` destination buffer inside the destination rectangle and the scissor box
+// with the source pixel mapping onto its center, passing its index to `write`. Rectangles with swapped corners mirror
+// the image.
+fn blit_plane<T: Copy>(
+    source: &BlitSource<T>,
+    (width, height): (usize, usize),
+    rects: BlitRects,
+    scissor: Scissor,
+    sample: impl Fn(&BlitSource<T>, f64, f64) -> Option<T>,
+    mut write: impl FnMut(usize, T),
+) {
+    let [src_x0, src_y0, src_x1, src_y1] = rects.src.map(|value| value as f64);
+    let [dst_x0, dst_y0, dst_x1, dst_y1] = rects.dst;
+    let clip = scissor.clip(width, height);
+    let clamp = |value: i32, low: usize, high: usize| (value.max(0) as usize).clamp(low, high);
+    let rect = Rect {
+        x0: clamp(dst_x0.min(dst_x1), clip.x0, clip.x1),
+        y0: clamp(dst_y0.min(dst_y1), clip.y0, clip.y1),
+        x1: clamp(dst_x0.max(dst_x1), clip.x0, clip.x1),
+        y1: clamp(dst_y0.max(dst_y1), clip.y0, clip.y1),
+    };
+    let scale_x = (src_x1 - src_x0) / (dst_x1 - dst_x0) as f64;
+    let scale_y = (src_y1 - src_y0) / (dst_y1 - dst_y0) as f64;
+    for y in rect.y0..rect.y1 {
+        let src_y = src_y0 + (y as f64 + 0.5 - dst_y0 as f64) * scale_y;
+        for x in rect.x0..rect.x1 {
+            let src_x = src_x0 + (x as f64 + 0.5 - dst_x0 as f64) * scale_x;
+            if let Some(value) = sample(source, src_x, src_y) {
+                write(y * width + x, value);
+            }
+        }
+    }
+}
+
+// Copies the buffers selected by `mask` from the read buffer of `read` to every draw buffer of `draw`.
+// Buffers missing from either framebuffer are skipped. Only the scissor test applies to the written pixels.
+fn blit_framebuffer(context: &mut GlContext, read: Framebuffer, draw: Framebuffer, rects: BlitRects, mask: GlBitfield, filter: u32) {
+    let known_bits = ClearBufferMask::COLOR as u32 | ClearBufferMask::DEPTH as u32 | ClearBufferMask::STENCIL as u32;
+    if mask & !known_bits != 0 {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let Some(filter) = Filter::from_u32(filter) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    let blit_color = mask & ClearBufferMask::COLOR as u32 != 0;
+    let blit_depth = mask & ClearBufferMask::DEPTH as u32 != 0;
+    let blit_stencil = mask & ClearBufferMask::STENCIL as u32 != 0;
+    // Depth and stencil values can't be interpolated
+    if filter == Filter::Linear && (blit_depth || blit_stencil) {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    if !check_framebuffer_complete(context, read) || !check_framebuffer_complete(context, draw) {
+        return;
+    }
+    // Multisampled buffers are copied sample by sample, which needs matching sample counts, or resolved.
+    // Neither can scale.
+    let read_samples = framebuffer_samples(context, read);
+    let draw_samples = framebuffer_samples(context, draw);
+    if read_samples > 0 && draw_samples > 0 && read_samples != draw_samples
+        || (read_samples > 0 || draw_samples > 0) && rects.src != rects.dst
+    {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    // Depth and stencil buffers have to have matching formats
+    if blit_depth
+        && let (Some(read_bits), Some(draw_bits)) = (with_depth_plane(context, read, |_, bits| bits), with_depth_plane(context, draw, |_, bits| bits))
+        && read_bits != draw_bits
+    {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    if blit_stencil
+        && let (Some(read_bits), Some(draw_bits)) = (with_stencil_plane(context, read, |_, bits| bits), with_stencil_plane(context, draw, |_, bits| bits))
+        && read_bits != draw_bits
+    {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    // Integer colors can only be copied between buffers of the same signedness, and can't be interpolated.
+    // Resolved colors have to be written to buffers of the same format.
+    if blit_color && let Some(read_format) = with_color_buffer(context, read, ColorSelect::Read, |buffer| buffer.format) {
+        let read_type = read_format.component_type();
+        if read_type.is_integer() && filter == Filter::Linear {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        for draw_buffer in 0..GL_MAX_DRAW_BUFFERS {
+            let Some(draw_format) = with_color_buffer(context, draw, ColorSelect::Draw(draw_buffer), |buffer| buffer.format) else {
+                continue;
+            };
+            let draw_type = draw_format.component_type();
+            let mismatched_type = (read_type.is_integer() || draw_type.is_integer()) && read_type != draw_type;
+            if mismatched_type || read_samples > 0 && read_format != draw_format {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
+            }
+        }
+    }
+    let scissor = context.scissor;
+    // With GL_FRAMEBUFFER_SRGB sRGB colors are decoded when read and encoded again when written,
+    // so scaling, filtering and resolving happens in linear color space. Depth and stencil values are resolved
+    // to their first sample.
+    let framebuffer_srgb = context.is_flag_enabled(Capability::FramebufferSrgb);
+    let sample = match filter {
+        Filter::Nearest => BlitSource::nearest,
+        Filter::Linear => BlitSource::linear,
+    };
+    // Each sample of a multisampled draw buffer is copied from the same sample of the read buffer, or from its only
+    // sample when that is single sampled
+    let draw_count = sample_count(draw_samples);
+    for draw_sample in 0..draw_count {
+        if blit_color
+            && let Some(source) = with_color_buffer(context, read, ColorSelect::Read, |buffer| {
+                let count = sample_count(buffer.samples);
+                BlitSource::copy((buffer.width, buffer.height), rects.src, |index| match draw_samples {
+                    0 => buffer.resolve(index, framebuffer_srgb),
+                    _ => buffer.get_linear(index * count + draw_sample % count, framebuffer_srgb),
+                })
+            })
+        {
+            for draw_buffer in 0..GL_MAX_DRAW_BUFFERS {
+                with_color_buffer(context, draw, ColorSelect::Draw(draw_buffer), |buffer| {
+                    blit_plane(&source, (buffer.width, buffer.height), rects, scissor, sample, |index, value| {
+                        let value = buffer.srgb_encoded(value, framebuffer_srgb);
+                        buffer.set(index * draw_count + draw_sample, value);
+                    });
+                });
+            }
+        }
+        if blit_depth
+            && let Some(source) = with_depth_plane(context, read, |plane, _| {
+                let count = sample_count(plane.samples);
+                BlitSource::copy((plane.width, plane.height), rects.src, |index| plane.pixels[index * count + draw_sample % count])
+            })
+        {
+            with_depth_plane(context, draw, |plane, _| {
+                blit_plane(&source, (plane.width, plane.height), rects, scissor, BlitSource::nearest, |index, value| {
+                    plane.pixels[index * draw_count + draw_sample] = value;
+                });
+            });
+        }
+        if blit_stencil
+            && let Some(source) = with_stencil_plane(context, read, |plane, _| {
+                let count = sample_count(plane.samples);
+                BlitSource::copy((plane.width, plane.height), rects.src, |index| plane.pixels[index * count + draw_sample % count])
+            })
+        {
+            with_stencil_plane(context, draw, |plane, _| {
+                blit_plane(&source, (plane.width, plane.height), rects, scissor, BlitSource::nearest, |index, value| {
+                    plane.pixels[index * draw_count + draw_sample] = value;
+                });
+            });
+        }
     }
 }
 
@@ -287,7 +1379,12 @@ pub extern "C" fn glBlitFramebuffer(
     mask: GlBitfield,
     filter: u32,
 ) {
-    todo!()
+    with_current_context(|context| {
+        let read = context.framebuffer_state.read_framebuffer;
+        let draw = context.framebuffer_state.write_framebuffer;
+        let rects = BlitRects { src: [srcX0, srcY0, srcX1, srcY1], dst: [dstX0, dstY0, dstX1, dstY1] };
+        blit_framebuffer(context, read, draw, rects, mask, filter);
+    });
 }
 
 #[unsafe(no_mangle)]
@@ -305,5 +1402,99 @@ pub extern "C" fn glBlitNamedFramebuffer(
     mask: GlBitfield,
     filter: u32,
 ) {
-    todo!()
+    with_current_context(|context| {
+        let Some(read) = named_framebuffer(context, readFramebuffer) else {
+            return;
+        };
+        let Some(draw) = named_framebuffer(context, drawFramebuffer) else {
+            return;
+        };
+        let rects = BlitRects { src: [srcX0, srcY0, srcX1, srcY1], dst: [dstX0, dstY0, dstX1, dstY1] };
+        blit_framebuffer(context, read, draw, rects, mask, filter);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        enums::{InternalFormat, PixelFormat, PixelType},
+        pixels::glReadPixels,
+        renderbuffer::{glBindRenderbuffer, glGenRenderbuffers, glRenderbufferStorageMultisample},
+        texture::{glBindTexture, glGenTextures, glTexStorage2D},
+        KoriExt::{glKCreateContext, glKDestroyContext, glKMakeCurrent},
+    };
+
+    // Reads the single row of the layer of `texture` attached to the bound framebuffer
+    fn read_layer(texture: u32, layer: i32) -> [u8; 16] {
+        let framebuffer = FramebufferTypes::Framebuffer as u32;
+        let attachment = DrawBufferFBO::ColorAttachment0 as u32;
+        glFramebufferTextureLayer(framebuffer, attachment, texture, 0, layer);
+        let mut pixels = [0u8; 16];
+        glReadPixels(0, 0, 4, 1, PixelFormat::Rgba as u32, PixelType::UnsignedByte as u32, pixels.as_mut_ptr());
+        pixels
+    }
+
+    // Framebuffer with a 4x4 RGBA8 color renderbuffer of `samples` samples
+    fn color_framebuffer(samples: i32) -> u32 {
+        let (mut renderbuffer, mut fbo) = (0, 0);
+        glGenRenderbuffers(1, &mut renderbuffer);
+        glBindRenderbuffer(GL_RENDERBUFFER, renderbuffer);
+        glRenderbufferStorageMultisample(GL_RENDERBUFFER, samples, InternalFormat::Rgba8 as u32, 4, 4);
+        glGenFramebuffers(1, &mut fbo);
+        glBindFramebuffer(FramebufferTypes::Framebuffer as u32, fbo);
+        glFramebufferRenderbuffer(FramebufferTypes::Framebuffer as u32, DrawBufferFBO::ColorAttachment0 as u32, GL_RENDERBUFFER, renderbuffer);
+        fbo
+    }
+
+    #[test]
+    fn blits_between_multisampled_framebuffers() {
+        let context = glKCreateContext(8, 8, 0, 0);
+        glKMakeCurrent(context);
+        let (color, nearest) = (ClearBufferMask::COLOR as u32, Filter::Nearest as u32);
+        let (resolved, two_samples, draw, read) = (color_framebuffer(0), color_framebuffer(2), color_framebuffer(4), color_framebuffer(4));
+        glClearColor(0.0, 1.0, 0.0, 1.0);
+        glClear(color);
+        // Matching sample counts copy sample by sample, but can't scale
+        glBlitNamedFramebuffer(read, draw, 0, 0, 4, 4, 0, 0, 4, 4, color, nearest);
+        assert_eq!(glGetError(), ErrorCode::NoError as u32);
+        glBlitNamedFramebuffer(read, draw, 0, 0, 4, 4, 0, 0, 2, 2, color, nearest);
+        assert_eq!(glGetError(), ErrorCode::InvalidOperation as u32);
+        glBlitNamedFramebuffer(read, two_samples, 0, 0, 4, 4, 0, 0, 4, 4, color, nearest);
+        assert_eq!(glGetError(), ErrorCode::InvalidOperation as u32);
+        glBlitNamedFramebuffer(draw, resolved, 0, 0, 4, 4, 0, 0, 4, 4, color, nearest);
+        glBindFramebuffer(FramebufferTypes::Framebuffer as u32, resolved);
+        let mut pixels = [0u8; 64];
+        glReadPixels(0, 0, 4, 4, PixelFormat::Rgba as u32, PixelType::UnsignedByte as u32, pixels.as_mut_ptr());
+        assert_eq!(pixels, [0, 255, 0, 255].repeat(16).as_slice());
+        glKMakeCurrent(0);
+        glKDestroyContext(context);
+    }
+
+    #[test]
+    fn clears_a_1d_array_layer() {
+        let context = glKCreateContext(8, 8, 0, 0);
+        glKMakeCurrent(context);
+        let target = TextureTarget::Texture1DArray as u32;
+        let framebuffer = FramebufferTypes::Framebuffer as u32;
+        let (mut texture, mut fbo) = (0, 0);
+        glGenTextures(1, &mut texture);
+        glBindTexture(target, texture);
+        // Four texels wide with three layers
+        glTexStorage2D(target, 1, InternalFormat::Rgba8 as u32, 4, 3);
+        glGenFramebuffers(1, &mut fbo);
+        glBindFramebuffer(framebuffer, fbo);
+        glFramebufferTextureLayer(framebuffer, DrawBufferFBO::ColorAttachment0 as u32, texture, 0, 1);
+        assert_eq!(glCheckFramebufferStatus(framebuffer), FramebufferStatus::Complete as u32);
+        // A layer is a single row, not as many rows as there are layers
+        assert_eq!(with_current_context(|context| context.framebuffer_objects[&fbo].size()), Some((4, 1)));
+        glClearColor(1.0, 0.0, 0.0, 1.0);
+        glClear(ClearBufferMask::COLOR as u32);
+        assert_eq!(glGetError(), ErrorCode::NoError as u32);
+        assert_eq!(read_layer(texture, 1), [255, 0, 0, 255].repeat(4).as_slice());
+        assert_eq!(read_layer(texture, 0), [0; 16]);
+        assert_eq!(read_layer(texture, 2), [0; 16]);
+        glKMakeCurrent(0);
+        glKDestroyContext(context);
+    }
 }
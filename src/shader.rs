@@ -0,0 +1,119 @@
+use crate::{
+    context::with_current_context,
+    enums::{ErrorCode, ShaderParameter, ShaderType},
+    types::{GlBool, Program, Shader, ShaderObject},
+};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glCreateShader(shader_type: u32) -> u32 {
+    with_current_context(|context| {
+        let Some(shader_type) = ShaderType::from_u32(shader_type) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return 0;
+        };
+        let mut shared = context.shared.lock().unwrap();
+        shared
+            .shader_objects
+            .insert(ShaderObject::Shader(Shader { shader_type }))
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteShader(shader: u32) {
+    with_current_context(|context| {
+        if shader == 0 {
+            return;
+        }
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        match shared.shader_objects.objects.get(&shader) {
+            Some(ShaderObject::Shader(_)) => {
+                shared.shader_objects.objects.remove(&shader);
+            }
+            Some(ShaderObject::Program(_)) => context.set_error(ErrorCode::InvalidOperation),
+            None => context.set_error(ErrorCode::InvalidValue),
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsShader(shader: u32) -> GlBool {
+    with_current_context(|context| {
+        let shared = context.shared.lock().unwrap();
+        matches!(shared.shader_objects.objects.get(&shader), Some(ShaderObject::Shader(_))) as GlBool
+    })
+    .unwrap_or(0)
+}
+
+// Shaders can't be compiled yet, so they never have a source, a log or a successful compile
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetShaderiv(shader: u32, pname: u32, params: *mut i32) {
+    with_current_context(|context| {
+        let shared_state = context.shared.clone();
+        let shared = shared_state.lock().unwrap();
+        let shader = match shared.shader_objects.objects.get(&shader) {
+            Some(ShaderObject::Shader(shader)) => shader,
+            Some(ShaderObject::Program(_)) => {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
+            }
+            None => {
+                context.set_error(ErrorCode::InvalidValue);
+                return;
+            }
+        };
+        let Some(pname) = ShaderParameter::from_u32(pname) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let value = match pname {
+            ShaderParameter::ShaderType => shader.shader_type as i32,
+            ShaderParameter::DeleteStatus
+            | ShaderParameter::CompileStatus
+            | ShaderParameter::InfoLogLength
+            | ShaderParameter::ShaderSourceLength => 0,
+        };
+        if !params.is_null() {
+            unsafe { *params = value };
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glCreateProgram() -> u32 {
+    with_current_context(|context| {
+        let mut shared = context.shared.lock().unwrap();
+        shared
+            .shader_objects
+            .insert(ShaderObject::Program(Program {}))
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteProgram(program: u32) {
+    with_current_context(|context| {
+        if program == 0 {
+            return;
+        }
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        match shared.shader_objects.objects.get(&program) {
+            Some(ShaderObject::Program(_)) => {
+                shared.shader_objects.objects.remove(&program);
+            }
+            Some(ShaderObject::Shader(_)) => context.set_error(ErrorCode::InvalidOperation),
+            None => context.set_error(ErrorCode::InvalidValue),
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsProgram(program: u32) -> GlBool {
+    with_current_context(|context| {
+        let shared = context.shared.lock().unwrap();
+        matches!(shared.shader_objects.objects.get(&program), Some(ShaderObject::Program(_))) as GlBool
+    })
+    .unwrap_or(0)
+}
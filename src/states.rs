@@ -1,15 +1,161 @@
-use crate::{enums::Framebuffer, types::ColorValue};
+use std::array;
 
+use crate::{enums::Framebuffer, types::{impl_enabelable, ColorData, ColorValue}};
 
-#[derive(Debug, Clone, Copy, Default)]
+
+#[derive(Debug, Clone, Copy)]
 pub(crate) struct ClearState {
     pub color_clear_value: ColorValue,
     pub depth_clear_value: f32,
     pub stenctil_clear_value: u8,
 }
 
+impl Default for ClearState {
+    fn default() -> Self {
+        Self {
+            color_clear_value: ColorValue::default(),
+            depth_clear_value: 1.0,
+            stenctil_clear_value: 0,
+        }
+    }
+}
+
+// Channels written to a draw buffer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ColorMask {
+    pub red: bool,
+    pub green: bool,
+    pub blue: bool,
+    pub alpha: bool,
+}
+
+impl Default for ColorMask {
+    fn default() -> Self {
+        Self {
+            red: true,
+            green: true,
+            blue: true,
+            alpha: true,
+        }
+    }
+}
+
+impl ColorMask {
+    // Combines `old` and `new` so only the unmasked channels of `new` are written. `new` is converted to the
+    // representation of `old`.
+    pub(crate) fn apply(&self, old: ColorData, new: ColorData) -> ColorData {
+        match old {
+            ColorData::Float(old) => ColorData::Float(self.select(old, new.to_floats())),
+            ColorData::Int(old) => ColorData::Int(self.select(old, new.to_ints())),
+            ColorData::UnsignedInt(old) => ColorData::UnsignedInt(self.select(old, new.to_unsigned_ints())),
+        }
+    }
+
+    fn select<T: Copy>(&self, old: [T; 4], new: [T; 4]) -> [T; 4] {
+        let mask = [self.red, self.green, self.blue, self.alpha];
+        array::from_fn(|index| if mask[index] { new[index] } else { old[index] })
+    }
+}
+
 
 pub(crate) struct FramebufferState {
     pub read_framebuffer: Framebuffer,
     pub write_framebuffer: Framebuffer,
-}
\ No newline at end of file
+}
+
+// Buffer bound to an indexed binding point by glBindBufferBase or glBindBufferRange
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IndexedBufferBinding {
+    pub buffer: u32,
+    pub offset: usize,
+    // None binds the whole buffer, whatever its size
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DepthState {
+    pub enabled: bool,
+    pub write_mask: bool,
+}
+
+impl Default for DepthState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            write_mask: true,
+        }
+    }
+}
+
+impl_enabelable!(DepthState);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StencilState {
+    pub enabled: bool,
+    pub front_write_mask: u32,
+    pub back_write_mask: u32,
+}
+
+impl Default for StencilState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            front_write_mask: u32::MAX,
+            back_write_mask: u32::MAX,
+        }
+    }
+}
+
+impl_enabelable!(StencilState);
+
+// Blend state of a single draw buffer
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BlendState {
+    pub enabled: bool,
+}
+
+impl_enabelable!(BlendState);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CullState {
+    pub enabled: bool,
+}
+
+impl_enabelable!(CullState);
+
+// Capabilities which have no further state of their own
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EnableFlag {
+    pub enabled: bool,
+}
+
+impl_enabelable!(EnableFlag);
+
+// Layout of pixel data in application memory, set by glPixelStore* separately for packing and unpacking
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PixelStoreState {
+    pub swap_bytes: bool,
+    pub lsb_first: bool,
+    // Pixels per row, zero for the width of the transferred rectangle
+    pub row_length: i32,
+    pub skip_rows: i32,
+    pub skip_pixels: i32,
+    pub alignment: i32,
+    pub image_height: i32,
+    pub skip_images: i32,
+}
+
+impl Default for PixelStoreState {
+    fn default() -> Self {
+        Self {
+            swap_bytes: false,
+            lsb_first: false,
+            row_length: 0,
+            skip_rows: 0,
+            skip_pixels: 0,
+            alignment: 4,
+            image_height: 0,
+            skip_images: 0,
+        }
+    }
+}
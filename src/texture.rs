@@ -0,0 +1,250 @@
+use std::{
+    slice,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    context::{with_current_context, GlContext},
+    enums::{
+        ErrorCode, Framebuffer, FormatKind, InternalFormat, TextureTarget, GL_MAX_3D_TEXTURE_SIZE,
+        GL_MAX_ARRAY_TEXTURE_LAYERS, GL_MAX_COLOR_TEXTURE_SAMPLES, GL_MAX_DEPTH_TEXTURE_SAMPLES, GL_MAX_INTEGER_SAMPLES,
+        GL_MAX_TEXTURE_SIZE,
+    },
+    types::{GlBool, GlSizei, Texture},
+};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGenTextures(n: GlSizei, textures: *mut u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || textures.is_null() {
+            return;
+        }
+        let texture_ids = unsafe { slice::from_raw_parts_mut(textures, n as usize) };
+        let mut shared = context.shared.lock().unwrap();
+        for id in texture_ids.iter_mut() {
+            *id = shared.textures.insert(Arc::new(Mutex::new(Texture::new())));
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteTextures(n: GlSizei, textures: *const u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || textures.is_null() {
+            return;
+        }
+        let texture_ids = unsafe { slice::from_raw_parts(textures, n as usize) };
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        for &key in texture_ids {
+            if shared.textures.objects.remove(&key).is_none() {
+                continue;
+            }
+            // Bindings of the current context revert to zero. Other contexts keep their stale binding.
+            context.texture_bindings.retain(|_, bound| *bound != key);
+            // Like renderbuffers, the texture is only detached from the framebuffers bound in the current context
+            for framebuffer in [context.framebuffer_state.write_framebuffer, context.framebuffer_state.read_framebuffer] {
+                if let Framebuffer::UserDefined(fbo_id) = framebuffer
+                    && let Some(fbo) = context.framebuffer_objects.get_mut(&fbo_id)
+                {
+                    fbo.detach(|attachment| attachment.is_texture(key));
+                }
+            }
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsTexture(texture: u32) -> GlBool {
+    with_current_context(|context| {
+        let shared = context.shared.lock().unwrap();
+        // A name only becomes a texture once it has been bound.
+        shared
+            .textures
+            .objects
+            .get(&texture)
+            .is_some_and(|texture| texture.lock().unwrap().target.is_some()) as GlBool
+    })
+    .unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindTexture(target: u32, texture: u32) {
+    with_current_context(|context| {
+        let Some(target) = TextureTarget::from_u32(target) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        if texture == 0 {
+            context.texture_bindings.remove(&target);
+            return;
+        }
+        let shared_state = context.shared.clone();
+        let mut shared = shared_state.lock().unwrap();
+        let Some(texture_object) = shared.textures.objects.get_mut(&texture) else {
+            // Names must come from glGenTextures
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        };
+        let mut texture_object = texture_object.lock().unwrap();
+        match texture_object.target {
+            Some(texture_target) if texture_target != target => {
+                context.set_error(ErrorCode::InvalidOperation);
+                return;
+            }
+            _ => texture_object.target = Some(target),
+        }
+        context.texture_bindings.insert(target, texture);
+    });
+}
+
+// Allocates immutable storage for the texture bound to `target`.
+// `height` is the layer count of 1D array textures and `depth` the layer count of 2D and cube map array textures.
+fn tex_storage(context: &mut GlContext, target: TextureTarget, levels: GlSizei, internalformat: u32, width: GlSizei, height: GlSizei, depth: GlSizei) {
+    let Some(format) = InternalFormat::from_u32(internalformat) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return;
+    };
+    if levels < 1 || width < 1 || height < 1 || depth < 1 {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let (max_size, max_layers) = match target {
+        TextureTarget::Texture3D => (GL_MAX_3D_TEXTURE_SIZE, GL_MAX_3D_TEXTURE_SIZE),
+        _ => (GL_MAX_TEXTURE_SIZE, GL_MAX_ARRAY_TEXTURE_LAYERS),
+    };
+    // Dimensions that are reduced between mipmap levels, and the layer count of array textures
+    let (mipmapped, layers) = match target {
+        TextureTarget::Texture1DArray => (vec![width], height),
+        TextureTarget::Texture2DArray | TextureTarget::TextureCubeMapArray => (vec![width, height], depth),
+        _ => (vec![width, height, depth], 1),
+    };
+    if mipmapped.iter().any(|&size| size > max_size) || layers > max_layers {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let is_cube_map = matches!(target, TextureTarget::TextureCubeMap | TextureTarget::TextureCubeMapArray);
+    if is_cube_map && (width != height || depth % 6 != 0 && target == TextureTarget::TextureCubeMapArray) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let largest = mipmapped.into_iter().max().unwrap_or(1);
+    let max_levels = if target.has_single_level() { 1 } else { largest.ilog2() as GlSizei + 1 };
+    if levels > max_levels {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    // Depth and stencil formats can't be used for 3D textures
+    if target == TextureTarget::Texture3D && format.kind() != FormatKind::Color {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let Some(texture) = context.texture_bindings.get(&target).copied() else {
+        // Texture zero has no storage
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    };
+    let Some(texture) = context.shared.lock().unwrap().textures.objects.get(&texture).cloned() else {
+        return;
+    };
+    let mut texture = texture.lock().unwrap();
+    if texture.immutable {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    if !texture.allocate(format, levels as usize, width as usize, height as usize, depth as usize, 0) {
+        context.set_error(ErrorCode::OutOfMemory);
+        return;
+    }
+    texture.immutable = true;
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glTexStorage1D(target: u32, levels: GlSizei, internalformat: u32, width: GlSizei) {
+    with_current_context(|context| match TextureTarget::from_u32(target) {
+        Some(target @ TextureTarget::Texture1D) => tex_storage(context, target, levels, internalformat, width, 1, 1),
+        _ => context.set_error(ErrorCode::InvalidEnum),
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glTexStorage2D(target: u32, levels: GlSizei, internalformat: u32, width: GlSizei, height: GlSizei) {
+    with_current_context(|context| match TextureTarget::from_u32(target) {
+        Some(
+            target @ (TextureTarget::Texture2D
+            | TextureTarget::Texture1DArray
+            | TextureTarget::TextureRectangle
+            | TextureTarget::TextureCubeMap),
+        ) => tex_storage(context, target, levels, internalformat, width, height, 1),
+        _ => context.set_error(ErrorCode::InvalidEnum),
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glTexStorage3D(target: u32, levels: GlSizei, internalformat: u32, width: GlSizei, height: GlSizei, depth: GlSizei) {
+    with_current_context(|context| match TextureTarget::from_u32(target) {
+        Some(
+            target @ (TextureTarget::Texture3D | TextureTarget::Texture2DArray | TextureTarget::TextureCubeMapArray),
+        ) => tex_storage(context, target, levels, internalformat, width, height, depth),
+        _ => context.set_error(ErrorCode::InvalidEnum),
+    });
+}
+
+// Allocates mutable storage with `samples` samples for the multisample texture bound to `target`
+#[unsafe(no_mangle)]
+pub extern "C" fn glTexImage2DMultisample(
+    target: u32,
+    samples: GlSizei,
+    internalformat: u32,
+    width: GlSizei,
+    height: GlSizei,
+    fixedsamplelocations: GlBool,
+) {
+    with_current_context(|context| {
+        let (Some(target @ TextureTarget::Texture2DMultisample), Some(format)) =
+            (TextureTarget::from_u32(target), InternalFormat::from_u32(internalformat))
+        else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let valid_size = 0..=GL_MAX_TEXTURE_SIZE;
+        if samples < 1 || !valid_size.contains(&width) || !valid_size.contains(&height) {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let max_samples = match format.kind() {
+            FormatKind::Color if format.component_type().is_integer() => GL_MAX_INTEGER_SAMPLES,
+            FormatKind::Color => GL_MAX_COLOR_TEXTURE_SAMPLES,
+            _ => GL_MAX_DEPTH_TEXTURE_SAMPLES,
+        };
+        if samples > max_samples {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        let Some(texture) = context.texture_bindings.get(&target).copied() else {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        };
+        let Some(texture) = context.shared.lock().unwrap().textures.objects.get(&texture).cloned() else {
+            return;
+        };
+        let mut texture = texture.lock().unwrap();
+        if texture.immutable {
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        if !texture.allocate(format, 1, width as usize, height as usize, 1, samples as u32) {
+            context.set_error(ErrorCode::OutOfMemory);
+            return;
+        }
+        texture.fixed_sample_locations = fixedsamplelocations != 0;
+    });
+}
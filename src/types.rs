@@ -1,6 +1,6 @@
-use std::array;
+use std::{array, sync::{Arc, Mutex}};
 
-use crate::enums::{DrawBufferFBO, DrawBufferSys, GL_MAX_COLOR_ATTACHMENTS};
+use crate::{format, states::ColorMask, enums::{AttachmentPoint, BufferUsage, DrawBufferFBO, DrawBufferSys, FormatKind, FramebufferStatus, InternalFormat, ShaderType, TextureTarget, VertexAttribType, GL_MAP_PERSISTENT_BIT, GL_MAX_COLOR_ATTACHMENTS, GL_MAX_VERTEX_ATTRIBS, GL_MAX_VERTEX_ATTRIB_BINDINGS}};
 
 // Not actually a u32. 32 single bit flags.
 pub type GlBitfield = u32;
@@ -8,6 +8,9 @@ pub type GlBitfield = u32;
 pub type GlBool = u8;
 // A signed integer but clamped to [0; i32::MAX]
 pub type GlSizei = i32;
+// Pointer sized sizes and offsets into buffer objects
+pub type GlSizeiptr = isize;
+pub type GlIntptr = isize;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
@@ -29,71 +32,782 @@ impl ColorValue {
     }
 }
 
+// Reallocates a `width` x `height` pixel grid. With `preserve` the overlapping region is copied over,
+// anchored at the origin, and newly exposed pixels are set to `fill`.
+fn resize_pixels<T: Copy>(pixels: &[T], old_width: usize, width: usize, height: usize, fill: T, preserve: bool) -> Vec<T> {
+    let mut resized = vec![fill; width * height];
+    if preserve && old_width > 0 {
+        for (new_row, old_row) in resized.chunks_exact_mut(width.max(1)).zip(pixels.chunks_exact(old_width)) {
+            let copied = width.min(old_width);
+            new_row[..copied].copy_from_slice(&old_row[..copied]);
+        }
+    }
+    resized
+}
+
+// Color as specified by the application, before it is converted to the format of a buffer
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ColorData {
+    Float([f32; 4]),
+    Int([i32; 4]),
+    UnsignedInt([u32; 4]),
+}
+
+impl ColorData {
+    // Components widened to f64, which represents every value of each variant exactly
+    pub(crate) fn components(self) -> [f64; 4] {
+        match self {
+            Self::Float(color) => color.map(f64::from),
+            Self::Int(color) => color.map(f64::from),
+            Self::UnsignedInt(color) => color.map(f64::from),
+        }
+    }
+
+    pub(crate) fn to_floats(self) -> [f32; 4] {
+        match self {
+            Self::Float(color) => color,
+            Self::Int(color) => color.map(|value| value as f32),
+            Self::UnsignedInt(color) => color.map(|value| value as f32),
+        }
+    }
+
+    // Float components are truncated, out of range integers saturate
+    pub(crate) fn to_ints(self) -> [i32; 4] {
+        match self {
+            Self::Float(color) => color.map(|value| value as i32),
+            Self::Int(color) => color,
+            Self::UnsignedInt(color) => color.map(|value| value.min(i32::MAX as u32) as i32),
+        }
+    }
+
+    pub(crate) fn to_unsigned_ints(self) -> [u32; 4] {
+        match self {
+            Self::Float(color) => color.map(|value| value as u32),
+            Self::Int(color) => color.map(|value| value.max(0) as u32),
+            Self::UnsignedInt(color) => color,
+        }
+    }
+}
+
+// Region of a buffer in window coordinates. The lower bounds are inclusive, the upper ones exclusive.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rect {
+    pub x0: usize,
+    pub y0: usize,
+    pub x1: usize,
+    pub y1: usize,
+}
+
+impl Rect {
+    // The part of the rect inside a `width` x `height` buffer
+    pub(crate) fn clamped(self, width: usize, height: usize) -> Rect {
+        let x1 = self.x1.min(width);
+        let y1 = self.y1.min(height);
+        Rect { x0: self.x0.min(x1), y0: self.y0.min(y1), x1, y1 }
+    }
+
+    // The rect over the values of a buffer storing `count` samples per pixel
+    fn widened(self, count: usize) -> Rect {
+        Rect { x0: self.x0 * count, x1: self.x1 * count, ..self }
+    }
+}
+
+// Values stored per pixel of a buffer with `samples` samples. Single sampled buffers have zero samples.
+// The samples of a pixel are stored next to each other.
+pub(crate) fn sample_count(samples: u32) -> usize {
+    samples.max(1) as usize
+}
+
+fn fill_rect<T: Copy>(pixels: &mut [T], width: usize, rect: Rect, value: T) {
+    for row in rect.y0..rect.y1 {
+        pixels[row * width + rect.x0..row * width + rect.x1].fill(value);
+    }
+}
+
+fn update_rect<T: Copy>(pixels: &mut [T], width: usize, rect: Rect, update: impl Fn(T) -> T) {
+    for row in rect.y0..rect.y1 {
+        for pixel in &mut pixels[row * width + rect.x0..row * width + rect.x1] {
+            *pixel = update(*pixel);
+        }
+    }
+}
+
 pub(crate) struct ColorBuffer {
     pub width: usize,
     pub height: usize,
-    pub pixels: Vec<ColorValue>,
+    pub format: InternalFormat,
+    pub samples: u32,
+    // Texels in the layout of `format`, see format.rs
+    pub data: Vec<u8>,
 }
 
 impl ColorBuffer {
-    pub(crate) fn new(width: usize, height: usize) -> Self {
+    pub(crate) fn new(format: InternalFormat, width: usize, height: usize, samples: u32) -> Self {
         Self {
             width,
             height,
-            pixels: vec![ColorValue::default(); width * height],
+            format,
+            samples,
+            data: vec![0u8; width * height * sample_count(samples) * format.texel_size()],
+        }
+    }
+
+    pub(crate) fn resize(&mut self, width: usize, height: usize, preserve: bool) {
+        // Rows are resized as rows of bytes, which keeps whole texels together
+        let pixel_size = sample_count(self.samples) * self.format.texel_size();
+        self.data = resize_pixels(&self.data, self.width * pixel_size, width * pixel_size, height, 0u8, preserve);
+        self.width = width;
+        self.height = height;
+    }
+
+    // Color of the sample at `index`, converted back from the format of the buffer. Single sampled buffers store
+    // one sample per pixel.
+    pub(crate) fn get(&self, index: usize) -> ColorData {
+        let texel_size = self.format.texel_size();
+        format::decode_color(self.format, &self.data[index * texel_size..(index + 1) * texel_size])
+    }
+
+    pub(crate) fn set(&mut self, index: usize, value: ColorData) {
+        let texel_size = self.format.texel_size();
+        format::encode_color(self.format, value, &mut self.data[index * texel_size..(index + 1) * texel_size]);
+    }
+
+    // Color of the sample at `index` in linear color space. Samples of sRGB formats are decoded when
+    // GL_FRAMEBUFFER_SRGB is enabled and read back as stored otherwise.
+    pub(crate) fn get_linear(&self, index: usize, framebuffer_srgb: bool) -> ColorData {
+        match self.get(index) {
+            ColorData::Float([red, green, blue, alpha]) if framebuffer_srgb && self.format.is_srgb() => {
+                let [red, green, blue] = [red, green, blue].map(format::srgb_to_linear);
+                ColorData::Float([red, green, blue, alpha])
+            }
+            value => value,
+        }
+    }
+
+    // Color of the pixel at `pixel`, the average of its samples as read by `get_linear`.
+    // Integer colors can't be averaged and resolve to their first sample.
+    pub(crate) fn resolve(&self, pixel: usize, framebuffer_srgb: bool) -> ColorData {
+        let count = sample_count(self.samples);
+        let first = self.get_linear(pixel * count, framebuffer_srgb);
+        if count == 1 || !matches!(first, ColorData::Float(_)) {
+            return first;
+        }
+        let mut sum = [0f32; 4];
+        for index in pixel * count..(pixel + 1) * count {
+            let color = self.get_linear(index, framebuffer_srgb).to_floats();
+            sum.iter_mut().zip(color).for_each(|(sum, value)| *sum += value);
+        }
+        ColorData::Float(sum.map(|sum| sum / count as f32))
+    }
+
+    // Resolves every pixel into the single sampled `target` of the same size and format.
+    // sRGB colors are averaged in linear color space.
+    pub(crate) fn resolve_into(&self, target: &mut ColorBuffer) {
+        for pixel in 0..self.width * self.height {
+            let value = target.srgb_encoded(self.resolve(pixel, true), true);
+            target.set(pixel, value);
         }
     }
+
+    // The value written for the linear color `value`, encoded to sRGB under the same conditions `get_linear` decodes
+    pub(crate) fn srgb_encoded(&self, value: ColorData, framebuffer_srgb: bool) -> ColorData {
+        match value {
+            ColorData::Float([red, green, blue, alpha]) if framebuffer_srgb && self.format.is_srgb() => {
+                let [red, green, blue] = [red, green, blue].map(format::linear_to_srgb);
+                ColorData::Float([red, green, blue, alpha])
+            }
+            value => value,
+        }
+    }
+
+    // Every sample of the pixels inside `rect` is written
+    pub(crate) fn fill_rect(&mut self, rect: Rect, value: ColorData) {
+        let count = sample_count(self.samples);
+        let (rect, width) = (rect.widened(count), self.width * count);
+        let texel_size = self.format.texel_size();
+        let mut texel = [0u8; 16];
+        format::encode_color(self.format, value, &mut texel);
+        for row in rect.y0..rect.y1 {
+            let row = &mut self.data[(row * width + rect.x0) * texel_size..(row * width + rect.x1) * texel_size];
+            for sample in row.chunks_exact_mut(texel_size) {
+                sample.copy_from_slice(&texel[..texel_size]);
+            }
+        }
+    }
+
+    pub(crate) fn fill_rect_masked(&mut self, rect: Rect, value: ColorData, mask: ColorMask) {
+        if mask == ColorMask::default() {
+            self.fill_rect(rect, value);
+            return;
+        }
+        let count = sample_count(self.samples);
+        let (rect, width) = (rect.widened(count), self.width * count);
+        for row in rect.y0..rect.y1 {
+            for index in row * width + rect.x0..row * width + rect.x1 {
+                let old = self.get(index);
+                self.set(index, mask.apply(old, value));
+            }
+        }
+    }
+}
+
+// Depth as stored in a depth buffer with `bits` bits. Fixed point buffers only keep the values they can represent,
+// 32 bit buffers are float and keep any value.
+pub(crate) fn quantize_depth(value: f32, bits: u32) -> f32 {
+    if bits == 32 {
+        return value;
+    }
+    let max = ((1u64 << bits) - 1) as f64;
+    ((value.clamp(0.0, 1.0) as f64 * max).round() / max) as f32
 }
 
 pub(crate) struct DepthBuffer {
     pub width: usize,
     pub height: usize,
+    pub samples: u32,
     pub pixels: Vec<f32>,
 }
 
 impl DepthBuffer {
-    pub(crate) fn new(width: usize, height: usize) -> Self {
+    pub(crate) fn new(width: usize, height: usize, samples: u32) -> Self {
         Self {
             width,
             height,
-            pixels: vec![0f32; width * height],
+            samples,
+            pixels: vec![0f32; width * height * sample_count(samples)],
         }
     }
+
+    pub(crate) fn resize(&mut self, width: usize, height: usize, preserve: bool) {
+        let count = sample_count(self.samples);
+        self.pixels = resize_pixels(&self.pixels, self.width * count, width * count, height, 0f32, preserve);
+        self.width = width;
+        self.height = height;
+    }
+
+    pub(crate) fn fill_rect(&mut self, rect: Rect, value: f32) {
+        let count = sample_count(self.samples);
+        fill_rect(&mut self.pixels, self.width * count, rect.widened(count), value);
+    }
 }
 
 pub(crate) struct StencilBuffer {
     pub width: usize,
     pub height: usize,
+    pub samples: u32,
     pub pixels: Vec<u8>,
 }
 
+impl StencilBuffer {
+    pub(crate) fn new(width: usize, height: usize, samples: u32) -> Self {
+        Self {
+            width,
+            height,
+            samples,
+            pixels: vec![0u8; width * height * sample_count(samples)],
+        }
+    }
+
+    pub(crate) fn resize(&mut self, width: usize, height: usize, preserve: bool) {
+        let count = sample_count(self.samples);
+        self.pixels = resize_pixels(&self.pixels, self.width * count, width * count, height, 0u8, preserve);
+        self.width = width;
+        self.height = height;
+    }
+
+    // Only the bits set in `mask` are written
+    pub(crate) fn fill_rect_masked(&mut self, rect: Rect, value: u8, mask: u8) {
+        let count = sample_count(self.samples);
+        update_rect(&mut self.pixels, self.width * count, rect.widened(count), |old| (old & !mask) | (value & mask));
+    }
+}
+
 pub(crate) struct DepthStencilBuffer {
     pub width: usize,
     pub height: usize,
+    pub samples: u32,
     pub depth: Vec<f32>,
     pub stencil: Vec<u8>,
 }
 
-pub(crate) struct FBO {
+impl DepthStencilBuffer {
+    pub(crate) fn new(width: usize, height: usize, samples: u32) -> Self {
+        let values = width * height * sample_count(samples);
+        Self {
+            width,
+            height,
+            samples,
+            depth: vec![0f32; values],
+            stencil: vec![0u8; values],
+        }
+    }
+
+    pub(crate) fn fill_depth_rect(&mut self, rect: Rect, value: f32) {
+        let count = sample_count(self.samples);
+        fill_rect(&mut self.depth, self.width * count, rect.widened(count), value);
+    }
+
+    pub(crate) fn fill_stencil_rect_masked(&mut self, rect: Rect, value: u8, mask: u8) {
+        let count = sample_count(self.samples);
+        update_rect(&mut self.stencil, self.width * count, rect.widened(count), |old| (old & !mask) | (value & mask));
+    }
+}
+
+// Whether `size` bytes can be allocated right now. Images are allocated in several pieces, so their size is checked
+// up front instead of failing halfway through.
+pub(crate) fn can_allocate(size: usize) -> bool {
+    Vec::<u8>::new().try_reserve_exact(size).is_ok()
+}
+
+// Storage of a renderbuffer or texture image in whichever buffer its internal format needs
+pub(crate) enum Image {
+    Color(ColorBuffer),
+    Depth(DepthBuffer),
+    Stencil(StencilBuffer),
+    DepthStencil(DepthStencilBuffer),
+}
+
+impl Image {
+    pub(crate) fn new(format: InternalFormat, width: usize, height: usize, samples: u32) -> Self {
+        match format.kind() {
+            FormatKind::Color => Self::Color(ColorBuffer::new(format, width, height, samples)),
+            FormatKind::Depth => Self::Depth(DepthBuffer::new(width, height, samples)),
+            FormatKind::Stencil => Self::Stencil(StencilBuffer::new(width, height, samples)),
+            FormatKind::DepthStencil => Self::DepthStencil(DepthStencilBuffer::new(width, height, samples)),
+        }
+    }
+
+    // Bytes allocated by `new`, saturating at usize::MAX
+    pub(crate) fn storage_size(format: InternalFormat, width: usize, height: usize, samples: u32) -> usize {
+        let sample_size = match format.kind() {
+            FormatKind::Color => format.texel_size(),
+            FormatKind::Depth => size_of::<f32>(),
+            FormatKind::Stencil => size_of::<u8>(),
+            FormatKind::DepthStencil => size_of::<f32>() + size_of::<u8>(),
+        };
+        [width, height, sample_count(samples)].into_iter().fold(sample_size, usize::saturating_mul)
+    }
+
+    pub(crate) fn size(&self) -> (usize, usize) {
+        match self {
+            Self::Color(buffer) => (buffer.width, buffer.height),
+            Self::Depth(buffer) => (buffer.width, buffer.height),
+            Self::Stencil(buffer) => (buffer.width, buffer.height),
+            Self::DepthStencil(buffer) => (buffer.width, buffer.height),
+        }
+    }
+
+    // The fill functions leave images without the matching component untouched
+    pub(crate) fn fill_color_rect_masked(&mut self, rect: Rect, value: ColorData, mask: ColorMask, framebuffer_srgb: bool) {
+        if let Self::Color(buffer) = self {
+            buffer.fill_rect_masked(rect, buffer.srgb_encoded(value, framebuffer_srgb), mask);
+        }
+    }
+
+    pub(crate) fn fill_depth_rect(&mut self, rect: Rect, value: f32) {
+        match self {
+            Self::Depth(buffer) => buffer.fill_rect(rect, value),
+            Self::DepthStencil(buffer) => buffer.fill_depth_rect(rect, value),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn fill_stencil_rect_masked(&mut self, rect: Rect, value: u8, mask: u8) {
+        match self {
+            Self::Stencil(buffer) => buffer.fill_rect_masked(rect, value, mask),
+            Self::DepthStencil(buffer) => buffer.fill_stencil_rect_masked(rect, value, mask),
+            _ => {}
+        }
+    }
+}
+
+// Range of a buffer mapped by glMapBufferRange
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BufferMapping {
+    pub offset: usize,
+    pub length: usize,
+    // GL_MAP_*_BIT flags the buffer was mapped with
+    pub access: GlBitfield,
+}
+
+pub(crate) struct Buffer {
+    // Set by the first glBindBuffer. Until then the name is only reserved.
+    pub created: bool,
+    // Mapped pointers point straight into `data`, so it must not be reallocated while the buffer is mapped
+    pub data: Vec<u8>,
+    pub usage: BufferUsage,
+    // Set by glBufferStorage, after which the storage can't be redefined
+    pub immutable: bool,
+    // GL_*_BIT flags of glBufferStorage
+    pub storage_flags: GlBitfield,
+    pub mapping: Option<BufferMapping>,
+}
+
+impl Buffer {
+    pub(crate) fn new() -> Self {
+        Self {
+            created: false,
+            data: Vec::new(),
+            usage: BufferUsage::StaticDraw,
+            immutable: false,
+            storage_flags: 0,
+            mapping: None,
+        }
+    }
+
+    // Buffers mapped without GL_MAP_PERSISTENT_BIT can't be used by any other command until unmapped
+    pub(crate) fn is_mapped_exclusively(&self) -> bool {
+        self.mapping.is_some_and(|mapping| mapping.access & GL_MAP_PERSISTENT_BIT == 0)
+    }
+}
+
+// Format of one generic vertex attribute, set by glVertexAttribFormat, and the binding it reads from
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VertexAttrib {
+    pub enabled: bool,
+    // Components per vertex. GL_BGRA attributes have four.
+    pub size: usize,
+    pub bgra: bool,
+    pub attrib_type: VertexAttribType,
+    pub normalized: bool,
+    // Set by glVertexAttribIFormat. The components are passed to the shader as integers.
+    pub integer: bool,
+    // Offset of the attribute from the start of each vertex of its binding
+    pub relative_offset: usize,
+    // Index into the vertex buffer bindings of the vertex array
+    pub binding: usize,
+    // Stride and pointer as passed to glVertexAttribPointer, only kept to be queried
+    pub stride: usize,
+    pub pointer: usize,
+}
+
+impl VertexAttrib {
+    fn new(index: usize) -> Self {
+        Self {
+            enabled: false,
+            size: 4,
+            bgra: false,
+            attrib_type: VertexAttribType::Float,
+            normalized: false,
+            integer: false,
+            relative_offset: 0,
+            binding: index,
+            stride: 0,
+            pointer: 0,
+        }
+    }
+
+    // Bytes of the attribute of a single vertex
+    pub(crate) fn element_size(&self) -> usize {
+        if self.attrib_type.is_packed() {
+            self.attrib_type.component_size()
+        } else {
+            self.size * self.attrib_type.component_size()
+        }
+    }
+}
+
+// Buffer range vertex attributes read from, set by glBindVertexBuffer
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct VertexBinding {
+    pub buffer: u32,
+    pub offset: usize,
+    // Unlike the stride of glVertexAttribPointer, zero makes every vertex read the same value
+    pub stride: usize,
+    // Instances sharing each value, zero to advance once per vertex instead
+    pub divisor: u32,
+}
+
+// Vertex array objects are container objects and stay per context like framebuffer objects.
+// Buffers are referenced by name.
+pub(crate) struct VertexArray {
+    pub attribs: [VertexAttrib; GL_MAX_VERTEX_ATTRIBS],
+    pub bindings: [VertexBinding; GL_MAX_VERTEX_ATTRIB_BINDINGS],
+    pub element_array_buffer: u32,
+}
+
+impl VertexArray {
+    pub(crate) fn new() -> Self {
+        Self {
+            // Attributes start out reading from the binding with the same index
+            attribs: array::from_fn(VertexAttrib::new),
+            bindings: [VertexBinding::default(); GL_MAX_VERTEX_ATTRIB_BINDINGS],
+            element_array_buffer: 0,
+        }
+    }
+}
+
+pub(crate) struct Renderbuffer {
+    // None until glRenderbufferStorage is called
+    pub internal_format: Option<InternalFormat>,
     pub width: usize,
     pub height: usize,
-    pub color_attachments: [ColorBuffer; GL_MAX_COLOR_ATTACHMENTS],
-    pub depth_attachment: Option<DepthBuffer>,
-    pub stencil_attachment: Option<StencilBuffer>,
-    pub depth_stencil_attachment: Option<DepthStencilBuffer>,
+    pub samples: u32,
+    pub image: Option<Image>,
+}
+
+impl Renderbuffer {
+    pub(crate) fn new() -> Self {
+        Self {
+            internal_format: None,
+            width: 0,
+            height: 0,
+            samples: 0,
+            image: None,
+        }
+    }
+}
+
+// An image attached to a framebuffer object. The attachment shares the storage of the attached object,
+// so it stays valid even when the object's name is deleted.
+#[derive(Clone)]
+pub(crate) enum Attachment {
+    Renderbuffer {
+        name: u32,
+        renderbuffer: Arc<Mutex<Renderbuffer>>,
+    },
+    Texture {
+        name: u32,
+        texture: Arc<Mutex<Texture>>,
+        level: usize,
+        // Layer, cube map face or 3D slice. None attaches every layer of a layered texture.
+        layer: Option<usize>,
+    },
+}
+
+impl Attachment {
+    // Runs `f` on the attached image, the first layer of a layered attachment.
+    // Returns None when the attached object has no storage for it.
+    pub(crate) fn with_image<R>(&self, f: impl FnOnce(&mut Image) -> R) -> Option<R> {
+        match self {
+            Self::Renderbuffer { renderbuffer, .. } => renderbuffer.lock().unwrap().image.as_mut().map(f),
+            Self::Texture { texture, level, layer, .. } => {
+                let mut texture = texture.lock().unwrap();
+                texture.levels.get_mut(*level)?.images.get_mut(layer.unwrap_or(0)).map(f)
+            }
+        }
+    }
+
+    // Runs `f` on every attached image, so once per layer of a layered attachment
+    pub(crate) fn for_each_image(&self, f: impl FnMut(&mut Image)) {
+        match self {
+            Self::Texture { texture, level, layer: None, .. } => {
+                let mut texture = texture.lock().unwrap();
+                if let Some(level) = texture.levels.get_mut(*level) {
+                    level.images.iter_mut().for_each(f);
+                }
+            }
+            _ => {
+                self.with_image(f);
+            }
+        }
+    }
+
+    // Format and size of the attached image. None when the attached object has no storage for it.
+    pub(crate) fn image_info(&self) -> Option<ImageInfo> {
+        match self {
+            Self::Renderbuffer { renderbuffer, .. } => {
+                let renderbuffer = renderbuffer.lock().unwrap();
+                Some(ImageInfo {
+                    format: renderbuffer.internal_format?,
+                    width: renderbuffer.width,
+                    height: renderbuffer.height,
+                    samples: renderbuffer.samples,
+                    fixed_sample_locations: true,
+                })
+            }
+            Self::Texture { texture, level, layer, .. } => {
+                let texture = texture.lock().unwrap();
+                let texture_level = texture.levels.get(*level)?;
+                if layer.is_some_and(|layer| layer >= texture_level.images.len()) {
+                    return None;
+                }
+                Some(ImageInfo {
+                    format: texture.internal_format?,
+                    width: texture_level.width,
+                    height: texture_level.height,
+                    samples: texture.samples,
+                    fixed_sample_locations: texture.fixed_sample_locations,
+                })
+            }
+        }
+    }
+
+    // Target of the texture if every layer of it is attached
+    pub(crate) fn layered_target(&self) -> Option<TextureTarget> {
+        match self {
+            Self::Texture { texture, layer: None, .. } => texture.lock().unwrap().target.filter(TextureTarget::is_layered),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn same_image(&self, other: &Attachment) -> bool {
+        match (self, other) {
+            (Self::Renderbuffer { renderbuffer, .. }, Self::Renderbuffer { renderbuffer: other, .. }) => {
+                Arc::ptr_eq(renderbuffer, other)
+            }
+            (
+                Self::Texture { texture, level, layer, .. },
+                Self::Texture { texture: other, level: other_level, layer: other_layer, .. },
+            ) => Arc::ptr_eq(texture, other) && level == other_level && layer == other_layer,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_renderbuffer(&self, renderbuffer: u32) -> bool {
+        matches!(self, Self::Renderbuffer { name, .. } if *name == renderbuffer)
+    }
+
+    pub(crate) fn is_texture(&self, texture: u32) -> bool {
+        matches!(self, Self::Texture { name, .. } if *name == texture)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ImageInfo {
+    pub format: InternalFormat,
+    pub width: usize,
+    pub height: usize,
+    pub samples: u32,
+    // Renderbuffers always use fixed sample locations
+    pub fixed_sample_locations: bool,
+}
+
+pub(crate) struct FBO {
+    pub color_attachments: [Option<Attachment>; GL_MAX_COLOR_ATTACHMENTS],
+    // A depth stencil image is attached to both of these
+    pub depth_attachment: Option<Attachment>,
+    pub stencil_attachment: Option<Attachment>,
+    // Attachment written by each draw buffer
     pub draw_buffers: [Option<DrawBufferFBO>; GL_MAX_COLOR_ATTACHMENTS],
+    pub read_buffer: Option<DrawBufferFBO>,
+    // Set by glFramebufferParameteri, they describe the framebuffer while nothing is attached
+    pub default_width: usize,
+    pub default_height: usize,
+    pub default_layers: usize,
+    pub default_samples: u32,
+    pub default_fixed_sample_locations: bool,
 }
 
 impl FBO {
-    pub(crate) fn new(width: usize, height: usize) -> Self {
+    // A framebuffer without attachments, which doesn't allocate any storage
+    pub(crate) fn new() -> Self {
         Self {
-            width,
-            height,
-            color_attachments: array::from_fn(|_| ColorBuffer::new(width, height)),
+            color_attachments: array::from_fn(|_| None),
             depth_attachment: None,
             stencil_attachment: None,
-            depth_stencil_attachment: None,
-            draw_buffers: [None; GL_MAX_COLOR_ATTACHMENTS],
+            // Draw buffer zero and the read buffer start out on the first color attachment
+            draw_buffers: array::from_fn(|index| (index == 0).then_some(DrawBufferFBO::ColorAttachment0)),
+            read_buffer: Some(DrawBufferFBO::ColorAttachment0),
+            default_width: 0,
+            default_height: 0,
+            default_layers: 0,
+            default_samples: 0,
+            default_fixed_sample_locations: false,
+        }
+    }
+
+    pub(crate) fn color_attachment(&self, buffer: DrawBufferFBO) -> Option<&Attachment> {
+        self.color_attachments.get(buffer.get_attachment_index())?.as_ref()
+    }
+
+    // Every attachment, a depth stencil image attached to both points is returned twice
+    pub(crate) fn attachments(&self) -> impl Iterator<Item = &Attachment> {
+        self.color_attachments.iter().flatten().chain(&self.depth_attachment).chain(&self.stencil_attachment)
+    }
+
+    // Size of the area that can be rendered to, the intersection of all attached images.
+    // Without attachments it's the default size.
+    pub(crate) fn size(&self) -> (usize, usize) {
+        self.attachments()
+            .filter_map(Attachment::image_info)
+            .map(|info| (info.width, info.height))
+            .reduce(|(width, height), (other_width, other_height)| (width.min(other_width), height.min(other_height)))
+            .unwrap_or((self.default_width, self.default_height))
+    }
+
+    // Completeness of the framebuffer, checking the rules in the order the GL specification lists them
+    pub(crate) fn status(&self) -> FramebufferStatus {
+        let color = self.color_attachments.iter().flatten().map(|attachment| (attachment, [FormatKind::Color].as_slice()));
+        let depth = self.depth_attachment.iter().map(|attachment| (attachment, [FormatKind::Depth, FormatKind::DepthStencil].as_slice()));
+        let stencil = self.stencil_attachment.iter().map(|attachment| (attachment, [FormatKind::Stencil, FormatKind::DepthStencil].as_slice()));
+        let attachments: Vec<_> = color.chain(depth).chain(stencil).collect();
+        let mut images = Vec::with_capacity(attachments.len());
+        // Every attachment needs a non empty image of a format renderable at its attachment point
+        for (attachment, kinds) in &attachments {
+            match attachment.image_info() {
+                Some(info) if info.width > 0 && info.height > 0 && kinds.contains(&info.format.kind()) => images.push(info),
+                _ => return FramebufferStatus::IncompleteAttachment,
+            }
+        }
+        // Rendering without attachments needs a default size
+        if attachments.is_empty() && (self.default_width == 0 || self.default_height == 0) {
+            return FramebufferStatus::IncompleteMissingAttachment;
+        }
+        // Draw and read buffers selecting missing attachments don't make a framebuffer incomplete since GL 4.1,
+        // writes to them are discarded and reads from them fail
+        // Depth and stencil have to come from a single depth stencil image
+        if let (Some(depth), Some(stencil)) = (&self.depth_attachment, &self.stencil_attachment)
+            && !depth.same_image(stencil)
+        {
+            return FramebufferStatus::Unsupported;
+        }
+        let same_samples = |info: &ImageInfo| {
+            info.samples == images[0].samples && info.fixed_sample_locations == images[0].fixed_sample_locations
+        };
+        if !images.iter().all(same_samples) {
+            return FramebufferStatus::IncompleteMultisample;
+        }
+        // Either every attachment is layered, all from textures of the same target, or none is
+        let layered_targets: Vec<_> = attachments.iter().map(|(attachment, _)| attachment.layered_target()).collect();
+        if layered_targets.iter().any(|target| target != &layered_targets[0]) {
+            return FramebufferStatus::IncompleteLayerTargets;
+        }
+        FramebufferStatus::Complete
+    }
+
+    // Attaches `attachment` at `point`. None detaches whatever is attached there.
+    pub(crate) fn attach(&mut self, point: AttachmentPoint, attachment: Option<Attachment>) {
+        match point {
+            AttachmentPoint::Color(index) => self.color_attachments[index] = attachment,
+            AttachmentPoint::Depth => self.depth_attachment = attachment,
+            AttachmentPoint::Stencil => self.stencil_attachment = attachment,
+            AttachmentPoint::DepthStencil => {
+                self.depth_attachment = attachment.clone();
+                self.stencil_attachment = attachment;
+            }
+        }
+    }
+
+    // Detaches every attachment matching `is_attached`, used when the name of an attached object is deleted
+    pub(crate) fn detach(&mut self, is_attached: impl Fn(&Attachment) -> bool) {
+        let attachments = self.color_attachments.iter_mut().chain([&mut self.depth_attachment, &mut self.stencil_attachment]);
+        for attachment in attachments {
+            if attachment.as_ref().is_some_and(&is_attached) {
+                *attachment = None;
+            }
+        }
+    }
+}
+
+// Pixel format of a default framebuffer, chosen at context creation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SurfaceConfig {
+    pub color_format: InternalFormat,
+    pub depth_format: Option<InternalFormat>,
+    pub stencil_bits: u32,
+    pub srgb: bool,
+    pub samples: u32,
+    pub double_buffered: bool,
+}
+
+impl SurfaceConfig {
+    // Format of the color buffers. sRGB capable surfaces store their colors in the matching sRGB format.
+    pub(crate) fn buffer_format(&self) -> InternalFormat {
+        match self.color_format {
+            InternalFormat::Rgb8 if self.srgb => InternalFormat::Srgb8,
+            InternalFormat::Rgba8 if self.srgb => InternalFormat::Srgb8Alpha8,
+            format => format,
         }
     }
 }
@@ -101,75 +815,231 @@ impl FBO {
 pub(crate) struct DefaultFramebuffer {
     pub width: usize,
     pub height: usize,
+    pub config: SurfaceConfig,
     pub color_buffer_front: ColorBuffer,
     pub color_buffer_back: ColorBuffer,
     pub depth_buffer: Option<DepthBuffer>,
     pub stencil_buffer: Option<StencilBuffer>,
     pub draw_buffer: DrawBufferSys,
+    pub read_buffer: DrawBufferSys,
 }
 impl DefaultFramebuffer {
-    pub(crate) fn init(width: usize, height: usize, double_buffered: GlBool) -> Self {
+    // Only the buffer drawn to is multisampled. The back buffer of a double buffered surface is resolved into the
+    // single sampled front buffer when swapping, the front buffer of a single buffered surface is resolved when
+    // it is presented.
+    pub(crate) fn init(width: usize, height: usize, config: SurfaceConfig) -> Self {
+        let (front_samples, back_samples) =
+            if config.double_buffered { (0, config.samples) } else { (config.samples, 0) };
         Self {
             width,
             height,
-            color_buffer_front: ColorBuffer::new(width, height),
-            color_buffer_back: ColorBuffer::new(width, height),
-            depth_buffer: Some(DepthBuffer::new(width, height)),
-            stencil_buffer: None,
-            draw_buffer: if double_buffered != 0 {
+            config,
+            color_buffer_front: ColorBuffer::new(config.buffer_format(), width, height, front_samples),
+            color_buffer_back: ColorBuffer::new(config.buffer_format(), width, height, back_samples),
+            depth_buffer: config.depth_format.map(|_| DepthBuffer::new(width, height, config.samples)),
+            stencil_buffer: (config.stencil_bits != 0).then(|| StencilBuffer::new(width, height, config.samples)),
+            draw_buffer: if config.double_buffered {
+                DrawBufferSys::Back
+            } else {
+                DrawBufferSys::Front
+            },
+            read_buffer: if config.double_buffered {
                 DrawBufferSys::Back
             } else {
                 DrawBufferSys::Front
             },
         }
     }
-    pub fn as_slice_u8(&self, buffer: DrawBufferSys) -> Vec<u8> {
-        let capacity = self.height * self.width * 4;
-        let mut ret = Vec::<u8>::with_capacity(capacity);
+    // Color buffer selected by the draw buffer, if any
+    pub(crate) fn draw_color_buffer(&mut self) -> Option<&mut ColorBuffer> {
+        self.color_buffer(self.draw_buffer)
+    }
+
+    pub(crate) fn read_color_buffer(&mut self) -> Option<&mut ColorBuffer> {
+        self.color_buffer(self.read_buffer)
+    }
+
+    fn color_buffer(&mut self, buffer: DrawBufferSys) -> Option<&mut ColorBuffer> {
         match buffer {
-            crate::enums::DrawBufferSys::Front | crate::enums::DrawBufferSys::FrontLeft => unsafe {
-                ret.set_len(capacity);
-                let ptr = ret.as_mut_ptr();
-
-                for (i, pixel) in self.color_buffer_front.pixels.iter().enumerate() {
-                    let base = i * 4;
-                    *ptr.add(base) = (pixel.alpha * 255.0) as u8;
-                    *ptr.add(base + 1) = (pixel.blue * 255.0) as u8;
-                    *ptr.add(base + 2) = (pixel.green * 255.0) as u8;
-                    *ptr.add(base + 3) = (pixel.red * 255.0) as u8;
-                }
-            },
-            crate::enums::DrawBufferSys::Back | crate::enums::DrawBufferSys::BackLeft => unsafe {
-                ret.set_len(capacity);
-                let ptr = ret.as_mut_ptr();
-
-                for (i, pixel) in self.color_buffer_back.pixels.iter().enumerate() {
-                    let base = i * 4;
-                    *ptr.add(base) = (pixel.alpha * 255.0) as u8;
-                    *ptr.add(base + 1) = (pixel.blue * 255.0) as u8;
-                    *ptr.add(base + 2) = (pixel.green * 255.0) as u8;
-                    *ptr.add(base + 3) = (pixel.red * 255.0) as u8;
-                }
-            },
-            _ => {}
+            DrawBufferSys::Front | DrawBufferSys::FrontLeft => Some(&mut self.color_buffer_front),
+            DrawBufferSys::Back | DrawBufferSys::BackLeft => Some(&mut self.color_buffer_back),
+            // NONE, and stereo buffers which can never be selected
+            _ => None,
+        }
+    }
+
+    pub(crate) fn resize(&mut self, width: usize, height: usize, preserve: bool) {
+        self.color_buffer_front.resize(width, height, preserve);
+        self.color_buffer_back.resize(width, height, preserve);
+        if let Some(depth_buffer) = &mut self.depth_buffer {
+            depth_buffer.resize(width, height, preserve);
+        }
+        if let Some(stencil_buffer) = &mut self.stencil_buffer {
+            stencil_buffer.resize(width, height, preserve);
+        }
+        self.width = width;
+        self.height = height;
+    }
+
+    // Buffers are stored bottom row first like GL window coordinates, while the host expects the top row first.
+    fn flipped_index(&self, index: usize) -> usize {
+        let (x, y) = (index % self.width, index / self.width);
+        (self.height - 1 - y) * self.width + x
+    }
+
+    // Colors are presented as stored. sRGB buffers already hold encoded values, other buffers are shown without
+    // any conversion. Multisampled buffers are resolved the same way as when swapping.
+    pub fn as_slice_u8(&self, buffer: DrawBufferSys) -> Vec<u8> {
+        let color_buffer = match buffer {
+            DrawBufferSys::Front | DrawBufferSys::FrontLeft => &self.color_buffer_front,
+            DrawBufferSys::Back | DrawBufferSys::BackLeft => &self.color_buffer_back,
+            _ => return Vec::new(),
+        };
+        let mut ret = vec![0u8; self.height * self.width * 4];
+        for i in 0..self.width * self.height {
+            let value = color_buffer.srgb_encoded(color_buffer.resolve(i, true), true);
+            let [red, green, blue, alpha] = value.to_floats();
+            let base = self.flipped_index(i) * 4;
+            for (offset, value) in [alpha, blue, green, red].into_iter().enumerate() {
+                ret[base + offset] = format::unorm(value as f64, 8) as u8;
+            }
         }
         ret
     }
 }
 
-trait Enabelable {
+// One mipmap level of a texture
+pub(crate) struct TextureLevel {
+    // Size of each image
+    pub width: usize,
+    pub height: usize,
+    // One image per layer, cube map face or 3D slice, so the depth of the level is the number of images.
+    // Faces of a cube map array are stored layer by layer.
+    pub images: Vec<Image>,
+}
+
+pub(crate) struct Texture {
+    // Set by the first glBindTexture, after which the texture can't be bound to any other target.
+    pub target: Option<TextureTarget>,
+    pub internal_format: Option<InternalFormat>,
+    // Set by glTexStorage*, after which the storage can't be redefined
+    pub immutable: bool,
+    // Only multisample textures have samples, they always have a single level
+    pub samples: u32,
+    pub fixed_sample_locations: bool,
+    pub levels: Vec<TextureLevel>,
+}
+
+impl Texture {
+    pub(crate) fn new() -> Self {
+        Self {
+            target: None,
+            internal_format: None,
+            immutable: false,
+            samples: 0,
+            fixed_sample_locations: true,
+            levels: Vec::new(),
+        }
+    }
+
+    // Allocates `levels` zeroed mipmap levels with a base level of `width` x `height` x `depth`.
+    // The layer dimension of array textures is not reduced between levels.
+    // Returns false and leaves the texture unchanged when the storage can't be allocated.
+    pub(crate) fn allocate(
+        &mut self,
+        format: InternalFormat,
+        levels: usize,
+        width: usize,
+        height: usize,
+        depth: usize,
+        samples: u32,
+    ) -> bool {
+        let target = self.target.unwrap_or(TextureTarget::Texture2D);
+        // Width, height and image count of each level
+        let sizes: Vec<_> = (0..levels)
+            .map(|level| {
+                let width = (width >> level).max(1);
+                let height = match target {
+                    TextureTarget::Texture1DArray => height,
+                    _ => (height >> level).max(1),
+                };
+                let depth = match target {
+                    TextureTarget::Texture3D => (depth >> level).max(1),
+                    _ => depth,
+                };
+                // Each layer of a 1D array texture is a single row, `height` is its layer count
+                match target {
+                    TextureTarget::Texture1DArray => (width, 1, height),
+                    TextureTarget::TextureCubeMap => (width, height, 6),
+                    _ => (width, height, depth),
+                }
+            })
+            .collect();
+        let size = sizes.iter().fold(0usize, |size, &(width, height, image_count)| {
+            size.saturating_add(Image::storage_size(format, width, height, samples).saturating_mul(image_count))
+        });
+        if !can_allocate(size) {
+            return false;
+        }
+        self.internal_format = Some(format);
+        self.samples = samples;
+        self.levels = sizes
+            .into_iter()
+            .map(|(width, height, image_count)| TextureLevel {
+                width,
+                height,
+                images: (0..image_count).map(|_| Image::new(format, width, height, samples)).collect(),
+            })
+            .collect();
+        true
+    }
+}
+
+pub(crate) struct Shader {
+    pub shader_type: ShaderType,
+}
+
+pub(crate) struct Program {}
+
+// Shaders and programs share a single name space.
+pub(crate) enum ShaderObject {
+    Shader(Shader),
+    Program(Program),
+}
+
+pub(crate) trait Enabelable {
     fn enable(&mut self);
     fn disable(&mut self);
     fn get_state(&self) -> bool;
 }
 
+// Implements Enabelable for state structs which keep their capability in an `enabled` field
+macro_rules! impl_enabelable {
+    ($($state:ty),+ $(,)?) => {
+        $(
+            impl $crate::types::Enabelable for $state {
+                fn disable(&mut self) {
+                    self.enabled = false;
+                }
+                fn enable(&mut self) {
+                    self.enabled = true;
+                }
+                fn get_state(&self) -> bool {
+                    self.enabled
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_enabelable;
+
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct Scissor {
-    x: i32,
-    y: i32,
-    width: i32,
-    height: i32,
-    enabled: bool,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub enabled: bool,
 }
 
 impl Scissor {
@@ -182,16 +1052,37 @@ impl Scissor {
             enabled: false,
         }
     }
-}
 
-impl Enabelable for Scissor {
-    fn disable(&mut self) {
-        self.enabled = false;
-    }
-    fn enable(&mut self) {
-        self.enabled = true;
+    // Region affected by clears and rasterization in a `width` x `height` framebuffer
+    pub(crate) fn clip(&self, width: usize, height: usize) -> Rect {
+        if !self.enabled {
+            return Rect { x0: 0, y0: 0, x1: width, y1: height };
+        }
+        let x0 = (self.x.max(0) as usize).min(width);
+        let y0 = (self.y.max(0) as usize).min(height);
+        let x1 = ((self.x as i64 + self.width as i64).max(0) as usize).clamp(x0, width);
+        let y1 = ((self.y as i64 + self.height as i64).max(0) as usize).clamp(y0, height);
+        Rect { x0, y0, x1, y1 }
     }
-    fn get_state(&self) -> bool {
-        self.enabled
+}
+
+impl_enabelable!(Scissor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantizes_fixed_point_depth() {
+        assert_eq!(quantize_depth(0.5, 16), (32768.0 / 65535.0) as f32);
+        assert_eq!(quantize_depth(1e-6, 16), 0.0);
+        assert_eq!(quantize_depth(1.0, 24), 1.0);
+        assert_eq!(quantize_depth(-0.5, 24), 0.0);
+        assert_eq!(quantize_depth(0.1, 32), 0.1);
+        // Every stored 24 bit value reads back as itself
+        for value in [0.1f32, 0.5, 0.999_999, 1.0 - f32::EPSILON] {
+            let stored = quantize_depth(value, 24);
+            assert_eq!(quantize_depth(stored, 24), stored);
+        }
     }
 }
@@ -0,0 +1,717 @@
+use std::{
+    slice,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    buffer::create_buffer,
+    context::{with_current_context, GlContext},
+    enums::{
+        BufferTarget, ErrorCode, VertexAttribParameter, VertexAttribType, GL_BGRA, GL_MAX_VERTEX_ATTRIBS,
+        GL_MAX_VERTEX_ATTRIB_BINDINGS, GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET, GL_MAX_VERTEX_ATTRIB_STRIDE,
+        GL_VERTEX_ATTRIB_ARRAY_POINTER,
+    },
+    format::from_half_float,
+    types::{Buffer, ColorData, GlBool, GlIntptr, GlSizei, VertexArray, VertexAttrib, VertexBinding},
+};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGenVertexArrays(n: GlSizei, arrays: *mut u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || arrays.is_null() {
+            return;
+        }
+        let array_ids = unsafe { slice::from_raw_parts_mut(arrays, n as usize) };
+        for id in array_ids.iter_mut() {
+            let key = context.next_vertex_array_id;
+            *id = key;
+            context.vertex_arrays.insert(key, VertexArray::new());
+            context.next_vertex_array_id += 1;
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDeleteVertexArrays(n: GlSizei, arrays: *const u32) {
+    with_current_context(|context| {
+        if n < 0 {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if n == 0 || arrays.is_null() {
+            return;
+        }
+        let array_ids = unsafe { slice::from_raw_parts(arrays, n as usize) };
+        // Zero and unused names are silently ignored
+        for &key in array_ids.iter().filter(|&&key| key != 0) {
+            if context.vertex_arrays.remove(&key).is_some() && context.vertex_array_binding == key {
+                context.vertex_array_binding = 0;
+            }
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glIsVertexArray(array: u32) -> GlBool {
+    with_current_context(|context| (array != 0 && context.vertex_arrays.contains_key(&array)) as GlBool).unwrap_or(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindVertexArray(array: u32) {
+    with_current_context(|context| {
+        if !context.vertex_arrays.contains_key(&array) {
+            // Names must come from glGenVertexArrays
+            context.set_error(ErrorCode::InvalidOperation);
+            return;
+        }
+        context.vertex_array_binding = array;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glCreateVertexArrays(n: GlSizei, arrays: *mut u32) {
+    // Generated names already refer to vertex array objects
+    glGenVertexArrays(n, arrays);
+}
+
+// Checks that `vaobj` names a vertex array object for the glVertexArray* functions
+fn named_vertex_array(context: &mut GlContext, vaobj: u32) -> bool {
+    if !context.vertex_arrays.contains_key(&vaobj) {
+        context.set_error(ErrorCode::InvalidOperation);
+        return false;
+    }
+    true
+}
+
+// Format arguments of glVertexAttrib*Pointer and glVertexAttrib*Format
+struct AttribFormat {
+    size: i32,
+    attrib_type: u32,
+    normalized: bool,
+    integer: bool,
+}
+
+// Validates `format`, returning the component count, whether the components are in BGRA order and the type
+fn validate_format(context: &mut GlContext, format: &AttribFormat) -> Option<(usize, bool, VertexAttribType)> {
+    let AttribFormat { size, attrib_type, normalized, integer } = *format;
+    if !(1..=4).contains(&size) && (size != GL_BGRA || integer) {
+        context.set_error(ErrorCode::InvalidValue);
+        return None;
+    }
+    let Some(attrib_type) = VertexAttribType::from_u32(attrib_type).filter(|attrib_type| !integer || attrib_type.is_integer()) else {
+        context.set_error(ErrorCode::InvalidEnum);
+        return None;
+    };
+    let bgra = size == GL_BGRA;
+    // Packed types always have four components, and BGRA attributes are normalized bytes or packed words
+    let valid_packing = match (attrib_type.is_packed(), bgra) {
+        (true, _) => size == 4 || bgra,
+        (false, true) => attrib_type == VertexAttribType::UnsignedByte,
+        (false, false) => true,
+    };
+    if !valid_packing || bgra && !normalized {
+        context.set_error(ErrorCode::InvalidOperation);
+        return None;
+    }
+    Some((if bgra { 4 } else { size as usize }, bgra, attrib_type))
+}
+
+// Sets the format of attribute `index` of vertex array `vaobj`
+fn vertex_attrib_format(context: &mut GlContext, vaobj: u32, index: u32, format: AttribFormat, relative_offset: u32) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIBS || relative_offset > GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET as u32 {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let Some((size, bgra, attrib_type)) = validate_format(context, &format) else {
+        return;
+    };
+    let attrib = &mut context.vertex_arrays.get_mut(&vaobj).unwrap().attribs[index as usize];
+    attrib.size = size;
+    attrib.bgra = bgra;
+    attrib.attrib_type = attrib_type;
+    // Ignored for floating and fixed point types
+    attrib.normalized = format.normalized;
+    attrib.integer = format.integer;
+    attrib.relative_offset = relative_offset as usize;
+}
+
+// Makes attribute `index` of vertex array `vaobj` read from vertex buffer binding `binding`
+fn vertex_attrib_binding(context: &mut GlContext, vaobj: u32, index: u32, binding: u32) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIBS || binding as usize >= GL_MAX_VERTEX_ATTRIB_BINDINGS {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    context.vertex_arrays.get_mut(&vaobj).unwrap().attribs[index as usize].binding = binding as usize;
+}
+
+// Binds `offset` bytes into `buffer` to vertex buffer binding `index` of vertex array `vaobj`
+fn bind_vertex_buffer(context: &mut GlContext, vaobj: u32, index: u32, buffer: u32, offset: GlIntptr, stride: GlSizei) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIB_BINDINGS || offset < 0 || !(0..=GL_MAX_VERTEX_ATTRIB_STRIDE).contains(&stride) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    if buffer != 0 && !create_buffer(context, buffer) {
+        return;
+    }
+    let binding = &mut context.vertex_arrays.get_mut(&vaobj).unwrap().bindings[index as usize];
+    binding.buffer = buffer;
+    binding.offset = offset as usize;
+    binding.stride = stride as usize;
+}
+
+// Binds consecutive vertex buffer bindings starting at `first`. Null `buffers` unbinds them, and errors in one entry
+// only skip that entry.
+fn bind_vertex_buffers(
+    context: &mut GlContext,
+    vaobj: u32,
+    first: u32,
+    count: GlSizei,
+    buffers: *const u32,
+    offsets: *const GlIntptr,
+    strides: *const GlSizei,
+) {
+    if count < 0 {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    if first as usize + count as usize > GL_MAX_VERTEX_ATTRIB_BINDINGS {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    if count == 0 {
+        return;
+    }
+    if buffers.is_null() {
+        let bindings = &mut context.vertex_arrays.get_mut(&vaobj).unwrap().bindings;
+        for binding in &mut bindings[first as usize..first as usize + count as usize] {
+            binding.buffer = 0;
+            binding.offset = 0;
+            binding.stride = 16;
+        }
+        return;
+    }
+    if offsets.is_null() || strides.is_null() {
+        return;
+    }
+    let buffers = unsafe { slice::from_raw_parts(buffers, count as usize) };
+    let offsets = unsafe { slice::from_raw_parts(offsets, count as usize) };
+    let strides = unsafe { slice::from_raw_parts(strides, count as usize) };
+    for (index, ((&buffer, &offset), &stride)) in (first..).zip(buffers.iter().zip(offsets).zip(strides)) {
+        bind_vertex_buffer(context, vaobj, index, buffer, offset, stride);
+    }
+}
+
+fn vertex_binding_divisor(context: &mut GlContext, vaobj: u32, index: u32, divisor: u32) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIB_BINDINGS {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    context.vertex_arrays.get_mut(&vaobj).unwrap().bindings[index as usize].divisor = divisor;
+}
+
+// Validates the arguments of glVertexAttrib*Pointer, then sets the format of the attribute and binds the buffer bound
+// to GL_ARRAY_BUFFER to the binding with the same index. Client memory arrays are not supported, so `pointer` is
+// always an offset into that buffer.
+fn vertex_attrib_pointer(context: &mut GlContext, index: u32, format: AttribFormat, stride: GlSizei, pointer: *const u8) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIBS || !(0..=GL_MAX_VERTEX_ATTRIB_STRIDE).contains(&stride) {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    let Some((size, bgra, attrib_type)) = validate_format(context, &format) else {
+        return;
+    };
+    let buffer = context.buffer_binding(BufferTarget::Array);
+    if buffer == 0 && !pointer.is_null() {
+        context.set_error(ErrorCode::InvalidOperation);
+        return;
+    }
+    let vertex_array = context.vertex_array();
+    let attrib = &mut vertex_array.attribs[index as usize];
+    *attrib = VertexAttrib {
+        enabled: attrib.enabled,
+        size,
+        bgra,
+        attrib_type,
+        normalized: format.normalized,
+        integer: format.integer,
+        relative_offset: 0,
+        binding: index as usize,
+        stride: stride as usize,
+        pointer: pointer as usize,
+    };
+    // Tightly packed attributes have a stride of zero
+    let stride = if stride == 0 { attrib.element_size() } else { stride as usize };
+    let binding = &mut vertex_array.bindings[index as usize];
+    binding.buffer = buffer;
+    binding.offset = pointer as usize;
+    binding.stride = stride;
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribPointer(index: u32, size: i32, attrib_type: u32, normalized: GlBool, stride: GlSizei, pointer: *const u8) {
+    let format = AttribFormat { size, attrib_type, normalized: normalized != 0, integer: false };
+    with_current_context(|context| vertex_attrib_pointer(context, index, format, stride, pointer));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribIPointer(index: u32, size: i32, attrib_type: u32, stride: GlSizei, pointer: *const u8) {
+    let format = AttribFormat { size, attrib_type, normalized: false, integer: true };
+    with_current_context(|context| vertex_attrib_pointer(context, index, format, stride, pointer));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribFormat(index: u32, size: i32, attrib_type: u32, normalized: GlBool, relative_offset: u32) {
+    let format = AttribFormat { size, attrib_type, normalized: normalized != 0, integer: false };
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        vertex_attrib_format(context, vaobj, index, format, relative_offset);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribIFormat(index: u32, size: i32, attrib_type: u32, relative_offset: u32) {
+    let format = AttribFormat { size, attrib_type, normalized: false, integer: true };
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        vertex_attrib_format(context, vaobj, index, format, relative_offset);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribBinding(index: u32, binding: u32) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        vertex_attrib_binding(context, vaobj, index, binding);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindVertexBuffer(index: u32, buffer: u32, offset: GlIntptr, stride: GlSizei) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        bind_vertex_buffer(context, vaobj, index, buffer, offset, stride);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glBindVertexBuffers(first: u32, count: GlSizei, buffers: *const u32, offsets: *const GlIntptr, strides: *const GlSizei) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        bind_vertex_buffers(context, vaobj, first, count, buffers, offsets, strides);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexBindingDivisor(index: u32, divisor: u32) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        vertex_binding_divisor(context, vaobj, index, divisor);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexAttribDivisor(index: u32, divisor: u32) {
+    with_current_context(|context| {
+        if index as usize >= GL_MAX_VERTEX_ATTRIBS {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        // Same as binding the attribute to the binding with its index and setting the divisor of that binding
+        let vertex_array = context.vertex_array();
+        vertex_array.attribs[index as usize].binding = index as usize;
+        vertex_array.bindings[index as usize].divisor = divisor;
+    });
+}
+
+fn set_attrib_enabled(context: &mut GlContext, vaobj: u32, index: u32, enabled: bool) {
+    if index as usize >= GL_MAX_VERTEX_ATTRIBS {
+        context.set_error(ErrorCode::InvalidValue);
+        return;
+    }
+    context.vertex_arrays.get_mut(&vaobj).unwrap().attribs[index as usize].enabled = enabled;
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glEnableVertexAttribArray(index: u32) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        set_attrib_enabled(context, vaobj, index, true);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDisableVertexAttribArray(index: u32) {
+    with_current_context(|context| {
+        let vaobj = context.vertex_array_binding;
+        set_attrib_enabled(context, vaobj, index, false);
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glEnableVertexArrayAttrib(vaobj: u32, index: u32) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            set_attrib_enabled(context, vaobj, index, true);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glDisableVertexArrayAttrib(vaobj: u32, index: u32) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            set_attrib_enabled(context, vaobj, index, false);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayAttribFormat(
+    vaobj: u32,
+    index: u32,
+    size: i32,
+    attrib_type: u32,
+    normalized: GlBool,
+    relative_offset: u32,
+) {
+    let format = AttribFormat { size, attrib_type, normalized: normalized != 0, integer: false };
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            vertex_attrib_format(context, vaobj, index, format, relative_offset);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayAttribIFormat(vaobj: u32, index: u32, size: i32, attrib_type: u32, relative_offset: u32) {
+    let format = AttribFormat { size, attrib_type, normalized: false, integer: true };
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            vertex_attrib_format(context, vaobj, index, format, relative_offset);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayAttribBinding(vaobj: u32, index: u32, binding: u32) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            vertex_attrib_binding(context, vaobj, index, binding);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayVertexBuffer(vaobj: u32, index: u32, buffer: u32, offset: GlIntptr, stride: GlSizei) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            bind_vertex_buffer(context, vaobj, index, buffer, offset, stride);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayVertexBuffers(
+    vaobj: u32,
+    first: u32,
+    count: GlSizei,
+    buffers: *const u32,
+    offsets: *const GlIntptr,
+    strides: *const GlSizei,
+) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            bind_vertex_buffers(context, vaobj, first, count, buffers, offsets, strides);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayBindingDivisor(vaobj: u32, index: u32, divisor: u32) {
+    with_current_context(|context| {
+        if named_vertex_array(context, vaobj) {
+            vertex_binding_divisor(context, vaobj, index, divisor);
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glVertexArrayElementBuffer(vaobj: u32, buffer: u32) {
+    with_current_context(|context| {
+        if !named_vertex_array(context, vaobj) || buffer != 0 && !create_buffer(context, buffer) {
+            return;
+        }
+        context.vertex_arrays.get_mut(&vaobj).unwrap().element_array_buffer = buffer;
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetVertexAttribiv(index: u32, pname: u32, params: *mut i32) {
+    with_current_context(|context| {
+        if index as usize >= GL_MAX_VERTEX_ATTRIBS {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        let Some(pname) = VertexAttribParameter::from_u32(pname) else {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        };
+        let vertex_array = context.vertex_array();
+        let attrib = vertex_array.attribs[index as usize];
+        let binding = vertex_array.bindings[attrib.binding];
+        let value = match pname {
+            VertexAttribParameter::Enabled => attrib.enabled as i32,
+            VertexAttribParameter::Size if attrib.bgra => GL_BGRA,
+            VertexAttribParameter::Size => attrib.size as i32,
+            VertexAttribParameter::Stride => attrib.stride as i32,
+            VertexAttribParameter::Type => attrib.attrib_type as i32,
+            VertexAttribParameter::Normalized => attrib.normalized as i32,
+            VertexAttribParameter::BufferBinding => binding.buffer as i32,
+            VertexAttribParameter::Integer => attrib.integer as i32,
+            VertexAttribParameter::Divisor => binding.divisor as i32,
+            VertexAttribParameter::Binding => attrib.binding as i32,
+            VertexAttribParameter::RelativeOffset => attrib.relative_offset as i32,
+        };
+        if !params.is_null() {
+            unsafe { *params = value };
+        }
+    });
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn glGetVertexAttribPointerv(index: u32, pname: u32, pointer: *mut *mut u8) {
+    with_current_context(|context| {
+        if index as usize >= GL_MAX_VERTEX_ATTRIBS {
+            context.set_error(ErrorCode::InvalidValue);
+            return;
+        }
+        if pname != GL_VERTEX_ATTRIB_ARRAY_POINTER {
+            context.set_error(ErrorCode::InvalidEnum);
+            return;
+        }
+        if !pointer.is_null() {
+            unsafe { *pointer = context.vertex_array().attribs[index as usize].pointer as *mut u8 };
+        }
+    });
+}
+
+// Decodes the attribute of a single vertex from `bytes`. Components the attribute doesn't have read as 0, or 1 for
+// the fourth one.
+fn decode_attrib(attrib: &VertexAttrib, bytes: &[u8]) -> ColorData {
+    let attrib_type = attrib.attrib_type;
+    let mut values = [0.0, 0.0, 0.0, 1.0];
+    // Normalized components are mapped to [0; 1], or [-1; 1] for signed types
+    let normalize = |value: f64, bits: u32| {
+        if !attrib.normalized {
+            value
+        } else if attrib_type.is_signed() {
+            (value / ((1u64 << (bits - 1)) - 1) as f64).max(-1.0)
+        } else {
+            value / ((1u64 << bits) - 1) as f64
+        }
+    };
+    if attrib_type.is_packed() {
+        let word = u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        // Red in the lowest ten bits, alpha in the highest two
+        for (index, value) in values.iter_mut().enumerate() {
+            let (shift, bits) = if index < 3 { (index as u32 * 10, 10) } else { (30, 2) };
+            let component = word << (32 - shift - bits);
+            let component = if attrib_type.is_signed() {
+                ((component as i32) >> (32 - bits)) as f64
+            } else {
+                (component >> (32 - bits)) as f64
+            };
+            *value = normalize(component, bits);
+        }
+    } else {
+        let component_size = attrib_type.component_size();
+        for (index, value) in values.iter_mut().enumerate().take(attrib.size) {
+            let bytes = &bytes[index * component_size..(index + 1) * component_size];
+            let bits = component_size as u32 * 8;
+            *value = match attrib_type {
+                VertexAttribType::Byte => normalize(bytes[0] as i8 as f64, bits),
+                VertexAttribType::UnsignedByte => normalize(bytes[0] as f64, bits),
+                VertexAttribType::Short => normalize(i16::from_ne_bytes([bytes[0], bytes[1]]) as f64, bits),
+                VertexAttribType::UnsignedShort => normalize(u16::from_ne_bytes([bytes[0], bytes[1]]) as f64, bits),
+                VertexAttribType::Int => normalize(i32::from_ne_bytes(bytes.try_into().unwrap()) as f64, bits),
+                VertexAttribType::UnsignedInt => normalize(u32::from_ne_bytes(bytes.try_into().unwrap()) as f64, bits),
+                VertexAttribType::Float => f32::from_ne_bytes(bytes.try_into().unwrap()) as f64,
+                VertexAttribType::Double => f64::from_ne_bytes(bytes.try_into().unwrap()),
+                VertexAttribType::HalfFloat => from_half_float(u16::from_ne_bytes([bytes[0], bytes[1]]) as u64) as f64,
+                // 16.16 fixed point
+                VertexAttribType::Fixed => i32::from_ne_bytes(bytes.try_into().unwrap()) as f64 / 65536.0,
+                VertexAttribType::UnsignedInt2101010Rev | VertexAttribType::Int2101010Rev => unreachable!(),
+            };
+        }
+    }
+    if attrib.bgra {
+        values.swap(0, 2);
+    }
+    match (attrib.integer, attrib_type.is_signed()) {
+        (false, _) => ColorData::Float(values.map(|value| value as f32)),
+        (true, true) => ColorData::Int(values.map(|value| value as i32)),
+        (true, false) => ColorData::UnsignedInt(values.map(|value| value as u32)),
+    }
+}
+
+// Input of disabled attributes and attributes reading outside of their buffer
+fn default_input(attrib: &VertexAttrib) -> ColorData {
+    match (attrib.integer, attrib.attrib_type.is_signed()) {
+        (false, _) => ColorData::Float([0.0, 0.0, 0.0, 1.0]),
+        (true, true) => ColorData::Int([0, 0, 0, 1]),
+        (true, false) => ColorData::UnsignedInt([0, 0, 0, 1]),
+    }
+}
+
+// Reads the vertex shader inputs of the bound vertex array object. The buffers of enabled attributes are looked up
+// once, so deleting them while fetching doesn't affect the fetched values.
+pub(crate) struct VertexFetch {
+    attribs: Vec<FetchedAttrib>,
+}
+
+// Format of an attribute with the binding it reads from and the buffer of that binding, if enabled
+type FetchedAttrib = (VertexAttrib, VertexBinding, Option<Arc<Mutex<Buffer>>>);
+
+impl VertexFetch {
+    pub(crate) fn new(context: &mut GlContext) -> Self {
+        let shared_state = context.shared.clone();
+        let shared = shared_state.lock().unwrap();
+        let vertex_array = context.vertex_array();
+        let attribs = vertex_array
+            .attribs
+            .iter()
+            .map(|attrib| {
+                let binding = vertex_array.bindings[attrib.binding];
+                let buffer = attrib.enabled.then(|| shared.buffers.objects.get(&binding.buffer).cloned()).flatten();
+                (*attrib, binding, buffer)
+            })
+            .collect();
+        Self { attribs }
+    }
+
+    // Whether an enabled attribute reads from a buffer that is mapped without GL_MAP_PERSISTENT_BIT
+    pub(crate) fn is_mapped_exclusively(&self) -> bool {
+        self.attribs.iter().flat_map(|(_, _, buffer)| buffer).any(|buffer| buffer.lock().unwrap().is_mapped_exclusively())
+    }
+
+    // Inputs of `vertex` of `instance`, one per generic attribute
+    pub(crate) fn fetch(&self, vertex: usize, instance: usize) -> [ColorData; GL_MAX_VERTEX_ATTRIBS] {
+        let mut inputs = [ColorData::Float([0.0, 0.0, 0.0, 1.0]); GL_MAX_VERTEX_ATTRIBS];
+        for (input, (attrib, binding, buffer)) in inputs.iter_mut().zip(&self.attribs) {
+            *input = default_input(attrib);
+            let Some(buffer) = buffer else {
+                continue;
+            };
+            // Instanced bindings advance once every `divisor` instances instead of once per vertex
+            let index = match binding.divisor {
+                0 => vertex,
+                divisor => instance / divisor as usize,
+            };
+            let buffer = buffer.lock().unwrap();
+            let start = binding.offset + index * binding.stride + attrib.relative_offset;
+            if let Some(bytes) = buffer.data.get(start..start + attrib.element_size()) {
+                *input = decode_attrib(attrib, bytes);
+            }
+        }
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrib(attrib_type: VertexAttribType, size: usize, normalized: bool) -> VertexAttrib {
+        VertexAttrib {
+            enabled: true,
+            size,
+            bgra: false,
+            attrib_type,
+            normalized,
+            integer: false,
+            relative_offset: 0,
+            binding: 0,
+            stride: 0,
+            pointer: 0,
+        }
+    }
+
+    fn floats(color: ColorData) -> [f32; 4] {
+        match color {
+            ColorData::Float(color) => color,
+            _ => panic!("expected a float color, got {color:?}"),
+        }
+    }
+
+    // Packs red, green, blue and alpha into a 2_10_10_10 word, red in the lowest bits
+    fn packed(red: u32, green: u32, blue: u32, alpha: u32) -> [u8; 4] {
+        (red | green << 10 | blue << 20 | alpha << 30).to_ne_bytes()
+    }
+
+    #[test]
+    fn clamps_normalized_signed_components() {
+        let bytes = [-128i8, -127, 127, 0].map(|value| value as u8);
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::Byte, 4, true), &bytes)), [-1.0, -1.0, 1.0, 0.0]);
+        let bytes: Vec<u8> = [i16::MIN, i16::MAX].iter().flat_map(|value| value.to_ne_bytes()).collect();
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::Short, 2, true), &bytes)), [-1.0, 1.0, 0.0, 1.0]);
+        let bytes = i32::MIN.to_ne_bytes();
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::Int, 1, true), &bytes)), [-1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn normalizes_unsigned_components() {
+        let bytes = [255, 51, 0, 0];
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::UnsignedByte, 3, true), &bytes)), [1.0, 0.2, 0.0, 1.0]);
+        let bytes = u16::MAX.to_ne_bytes();
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::UnsignedShort, 1, true), &bytes)), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn keeps_unnormalized_components() {
+        let bytes = [-5i8 as u8, 200];
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::Byte, 2, false), &bytes)), [-5.0, -56.0, 0.0, 1.0]);
+        let bytes = 0x0001_8000i32.to_ne_bytes();
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::Fixed, 1, false), &bytes)), [1.5, 0.0, 0.0, 1.0]);
+        let bytes = 0xc000u16.to_ne_bytes();
+        assert_eq!(floats(decode_attrib(&attrib(VertexAttribType::HalfFloat, 1, false), &bytes)), [-2.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn sign_extends_2_10_10_10_components() {
+        let signed = attrib(VertexAttribType::Int2101010Rev, 4, false);
+        assert_eq!(floats(decode_attrib(&signed, &packed(0x3ff, 0x200, 0x1ff, 0b10))), [-1.0, -512.0, 511.0, -2.0]);
+        let unsigned = attrib(VertexAttribType::UnsignedInt2101010Rev, 4, false);
+        assert_eq!(floats(decode_attrib(&unsigned, &packed(0x3ff, 0x200, 0x1ff, 0b10))), [1023.0, 512.0, 511.0, 2.0]);
+    }
+
+    #[test]
+    fn normalizes_2_10_10_10_components() {
+        let signed = attrib(VertexAttribType::Int2101010Rev, 4, true);
+        // The most negative value of each component clamps to -1
+        assert_eq!(floats(decode_attrib(&signed, &packed(0x200, 0x1ff, 0, 0b10))), [-1.0, 1.0, 0.0, -1.0]);
+        assert_eq!(floats(decode_attrib(&signed, &packed(0x3ff, 0, 0, 0b01))), [-1.0 / 511.0, 0.0, 0.0, 1.0]);
+        let unsigned = attrib(VertexAttribType::UnsignedInt2101010Rev, 4, true);
+        assert_eq!(floats(decode_attrib(&unsigned, &packed(0x3ff, 0, 0, 0b11))), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn swizzles_bgra() {
+        let bgra = VertexAttrib { bgra: true, ..attrib(VertexAttribType::UnsignedByte, 4, true) };
+        assert_eq!(floats(decode_attrib(&bgra, &[0, 51, 255, 255])), [1.0, 0.2, 0.0, 1.0]);
+        let bgra = VertexAttrib { bgra: true, ..attrib(VertexAttribType::UnsignedInt2101010Rev, 4, false) };
+        assert_eq!(floats(decode_attrib(&bgra, &packed(1, 2, 3, 0))), [3.0, 2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn keeps_integer_attributes() {
+        let signed = VertexAttrib { integer: true, ..attrib(VertexAttribType::Short, 2, false) };
+        let bytes: Vec<u8> = [-3i16, 7].iter().flat_map(|value| value.to_ne_bytes()).collect();
+        assert!(matches!(decode_attrib(&signed, &bytes), ColorData::Int([-3, 7, 0, 1])));
+        let unsigned = VertexAttrib { integer: true, ..attrib(VertexAttribType::UnsignedInt, 1, false) };
+        assert!(matches!(decode_attrib(&unsigned, &u32::MAX.to_ne_bytes()), ColorData::UnsignedInt([u32::MAX, 0, 0, 1])));
+    }
+}